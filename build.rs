@@ -0,0 +1,67 @@
+//! Generates the module list of the `aoc_all` binary, which runs every scaffolded solution in-process.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-env-changed=CARGO_FEATURE_DHAT_HEAP");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut bins: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let name = entry.ok()?.file_name().into_string().ok()?;
+                    let bin = name.strip_suffix(".rs")?;
+                    is_solution(bin).then(|| bin.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // every solution declares its own global allocator when profiling with dhat.
+    if env::var_os("CARGO_FEATURE_DHAT_HEAP").is_some() {
+        println!("cargo:warning=aoc_all runs no solutions when built with the `dhat-heap` feature, run the individual days instead.");
+        bins.clear();
+    }
+
+    bins.sort();
+
+    let mut out = String::new();
+
+    // unused items are only reported for the individual binaries.
+    for bin in &bins {
+        let path = bin_dir.join(format!("{bin}.rs"));
+        out.push_str(&format!(
+            "#[cfg(not(test))]\n#[allow(dead_code)]\n#[path = {:?}]\nmod day_{bin};\n",
+            path.display().to_string()
+        ));
+    }
+
+    let calls: Vec<String> = bins
+        .iter()
+        .map(|bin| format!("day_{bin}::__solution()"))
+        .collect();
+
+    out.push_str(&format!(
+        "\n#[cfg(not(test))]\nfn solutions() -> Vec<advent_of_code::template::registry::Solution> {{\n    vec![{}]\n}}\n",
+        calls.join(", ")
+    ));
+    out.push_str(
+        "\n#[cfg(test)]\nfn solutions() -> Vec<advent_of_code::template::registry::Solution> {\n    vec![]\n}\n",
+    );
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
+
+/// Solutions are named after their day, like `05`, or after their year and day, like `2023_05`.
+fn is_solution(bin: &str) -> bool {
+    let is_number = |s: &str, len: usize| s.len() == len && s.chars().all(|c| c.is_ascii_digit());
+
+    match bin.split_once('_') {
+        Some((year, day)) => is_number(year, 4) && is_number(day, 2),
+        None => is_number(bin, 2),
+    }
+}
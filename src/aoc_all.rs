@@ -0,0 +1,6 @@
+//! Runs every scaffolded solution in a single process. See `advent_of_code::template::registry`.
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+fn main() {
+    advent_of_code::template::registry::run(solutions());
+}
@@ -1,48 +1,30 @@
-use regex::Regex;
+use advent_of_code::captures_iter_typed;
 
 advent_of_code::solution!(3);
 
+const MUL: &str = r"mul\(([0-9]{1,3}),([0-9]{1,3})\)";
+
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut res = 0;
-    let re = Regex::new("mul\\(([0-9]{1,3}),([0-9]{1,3})\\)").unwrap();
-    for mat in re.find_iter(input) {
-        let start_idx = mat.start();
-        let mut inner_res: u32 = 1;
-        for g in re.captures_at(input, start_idx).unwrap().iter().skip(1) {
-            inner_res *= g.unwrap().as_str().parse::<u32>().unwrap();
-        }
-        res += inner_res;
-    }
-    Some(res)
+    Some(
+        captures_iter_typed::<(u32, u32)>(MUL, input)
+            .map(|(a, b)| a * b)
+            .sum(),
+    )
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
     let mut res = 0;
     let mut enabled = true;
-    let re = Regex::new("mul\\(([0-9]{1,3}),([0-9]{1,3})\\)|do\\(\\)|don't\\(\\)").unwrap();
-    for mat in re.find_iter(input) {
-        let start_idx = mat.start();
-        let mut inner_res: u32 = 1;
-        let unwrapped = re.captures_at(input, start_idx).unwrap();
-        let iter = unwrapped.iter();
-        let mut is_mul = false;
-        for g in iter.take(1) {
-            let s = g.unwrap().as_str();
-            if s.starts_with("don\'t") {
-                enabled = false;
-            } else if s.starts_with("do") {
-                enabled = true;
-            } else {
-                is_mul = true;
-            }
-        }
-        if !is_mul || !enabled {
-            continue;
-        }
-        for g in re.captures_at(input, start_idx).unwrap().iter().skip(1) {
-            inner_res *= g.unwrap().as_str().parse::<u32>().unwrap();
+    let pattern = format!(r"{MUL}|(do|don't)\(\)");
+    for (a, b, toggle) in
+        captures_iter_typed::<(Option<u32>, Option<u32>, Option<String>)>(&pattern, input)
+    {
+        match (a.zip(b), toggle.as_deref()) {
+            (Some((a, b)), _) if enabled => res += a * b,
+            (_, Some("do")) => enabled = true,
+            (_, Some("don't")) => enabled = false,
+            _ => {}
         }
-        res += inner_res;
     }
     Some(res)
 }
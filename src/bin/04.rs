@@ -1,74 +1,22 @@
-use std::str::Chars;
+use advent_of_code::grid::Grid;
 
 advent_of_code::solution!(4);
-fn transpose<T>(v: Vec<Vec<T>>) -> Vec<Vec<T>>
-where
-    T: Clone,
-{
-    assert!(!v.is_empty());
-    (0..v[0].len())
-        .map(|i| v.iter().map(|inner| inner[i].clone()).collect::<Vec<T>>())
-        .collect()
-}
 
-fn find_all_horizontal(cs: Vec<Vec<char>>, needle: Chars<'_>) -> u32 {
-    cs.iter()
-        .map(|c| {
-            let mut found = 0;
-            for window in c.windows(needle.clone().count()) {
-                if needle.clone().zip(window.iter()).all(|(c1, c2)| c1 == *c2) {
-                    found += 1;
-                }
-                if needle
-                    .clone()
-                    .zip(window.iter().rev())
-                    .all(|(c1, c2)| c1 == *c2)
-                {
-                    found += 1;
-                }
-            }
-            found
-        })
-        .sum()
-}
+const NEEDLE: [char; 4] = ['X', 'M', 'A', 'S'];
 
-pub fn get_diag_idx(inp: &[Vec<char>], idx1: usize, idx2: usize, len: usize) -> Vec<char> {
-    let mut result = vec![];
-    for i in 0..len {
-        if idx1 + i >= inp.len() || idx2 + i >= inp[idx1 + i].len() {
-            break;
+// counts the needle in both reading directions
+fn find_all<'a>(line: impl Iterator<Item = &'a char>, needle: &[char]) -> u32 {
+    let line = line.copied().collect::<Vec<_>>();
+    let mut found = 0;
+    for window in line.windows(needle.len()) {
+        if window == needle {
+            found += 1;
         }
-        result.push(inp[idx1 + i][idx2 + i]);
-    }
-    result
-}
-
-pub fn get_diag_idx_2(inp: &[Vec<char>], idx1: usize, idx2: usize, len: usize) -> Vec<char> {
-    let mut result = vec![];
-    for i in 0..len {
-        if idx1 + i >= inp.len() || i > idx2 || idx2 - i >= inp[idx1 + i].len() {
-            break;
+        if window.iter().rev().eq(needle.iter()) {
+            found += 1;
         }
-        result.push(inp[idx1 + i][idx2 - i]);
     }
-    result
-}
-
-pub fn get_diagonals(inp: &[Vec<char>], size: usize) -> Vec<Vec<char>> {
-    let mut result = vec![];
-    for diag_idx in 0..size {
-        result.push(get_diag_idx(inp, 0, diag_idx, size - diag_idx));
-    }
-    for diag_idx in 1..size {
-        result.push(get_diag_idx(inp, diag_idx, 0, size - diag_idx));
-    }
-    for diag_idx in 0..size {
-        result.push(get_diag_idx_2(inp, 0, diag_idx, diag_idx + 1));
-    }
-    for diag_idx in 1..size {
-        result.push(get_diag_idx_2(inp, diag_idx, size - 1, size - diag_idx));
-    }
-    result
+    found
 }
 
 // packs 4 (supposedly 8-bit) chars into a single int
@@ -79,65 +27,53 @@ fn char_signature(chars: [char; 4]) -> u64 {
         + ((chars[3] as u64) << 24)
 }
 
-fn extract_char_signature(inp: &[Vec<char>], i: usize, j: usize) -> Option<u64> {
-    if i + 1 >= inp.len() || i < 1 || j < 1 || j + 1 >= inp[i].len() {
-        None
-    } else {
-        Some(char_signature([
-            inp[i - 1][j - 1],
-            inp[i - 1][j + 1],
-            inp[i + 1][j - 1],
-            inp[i + 1][j + 1],
-        ]))
-    }
+fn extract_char_signature(grid: &Grid<char>, pos: (usize, usize)) -> Option<u64> {
+    Some(char_signature([
+        *grid.get_offset(pos, (-1, -1))?,
+        *grid.get_offset(pos, (-1, 1))?,
+        *grid.get_offset(pos, (1, -1))?,
+        *grid.get_offset(pos, (1, 1))?,
+    ]))
 }
 
-pub fn get_mas_cross(inp: &[Vec<char>]) -> u32 {
-    let mut res = 0;
-    let sig1 = char_signature(['M', 'M', 'S', 'S']);
-    let sig2 = char_signature(['M', 'S', 'M', 'S']);
-    let sig3 = char_signature(['S', 'S', 'M', 'M']);
-    let sig4 = char_signature(['S', 'M', 'S', 'M']);
-    for i in 0..inp.len() {
-        for j in 0..inp[i].len() {
-            if inp[i][j] != 'A' {
-                continue;
-            }
-            if let Some(sig) = extract_char_signature(inp, i, j) {
-                if sig == sig1 || sig == sig2 || sig == sig3 || sig == sig4 {
-                    res += 1;
-                }
-            }
-        }
-    }
-    res
+pub fn get_mas_cross(grid: &Grid<char>) -> u32 {
+    let sigs = [
+        char_signature(['M', 'M', 'S', 'S']),
+        char_signature(['M', 'S', 'M', 'S']),
+        char_signature(['S', 'S', 'M', 'M']),
+        char_signature(['S', 'M', 'S', 'M']),
+    ];
+    grid.iter()
+        .filter(|(_, c)| **c == 'A')
+        .filter_map(|(pos, _)| extract_char_signature(grid, pos))
+        .filter(|sig| sigs.contains(sig))
+        .count() as u32
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let vecs = input
-        .split_ascii_whitespace()
-        .filter(|v| !v.is_empty())
-        .map(|v| Vec::from_iter(v.chars()))
-        .collect::<Vec<_>>();
-    let row_len = input.split_ascii_whitespace().next().unwrap().len();
-    let transposed = transpose(vecs.clone());
-    let diags = get_diagonals(&vecs, row_len);
-    let needle = "XMAS".chars();
+    let grid: Grid<char> = input.parse().unwrap();
     Some(
-        find_all_horizontal(vecs, needle.clone())
-            + find_all_horizontal(transposed, needle.clone())
-            + find_all_horizontal(diags, needle.clone()),
+        grid.rows()
+            .map(|row| find_all(row.iter(), &NEEDLE))
+            .sum::<u32>()
+            + grid
+                .columns()
+                .map(|col| find_all(col, &NEEDLE))
+                .sum::<u32>()
+            + grid
+                .diagonals()
+                .map(|diag| find_all(diag, &NEEDLE))
+                .sum::<u32>()
+            + grid
+                .anti_diagonals()
+                .map(|diag| find_all(diag, &NEEDLE))
+                .sum::<u32>(),
     )
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let vecs = input
-        .split_ascii_whitespace()
-        .filter(|v| !v.is_empty())
-        .map(|v| Vec::from_iter(v.chars()))
-        .collect::<Vec<_>>();
-
-    Some(get_mas_cross(&vecs))
+    let grid: Grid<char> = input.parse().unwrap();
+    Some(get_mas_cross(&grid))
 }
 
 #[cfg(test)]
@@ -1,21 +1,16 @@
 use std::collections::HashMap;
 
+use advent_of_code::parse::{self, lines, sections, separated, separated_pair, tag, uint};
+
 advent_of_code::solution!(5);
 
-pub fn parse(input: &str) -> (Vec<(u32, u32)>, Vec<Vec<u32>>) {
-    let mut r1 = vec![];
-    let mut r2 = vec![];
-    for l in input.lines().filter(|v| !v.is_empty()) {
-        if l.contains("|") {
-            let mut it = l.split("|").map(|v| v.parse::<u32>().unwrap());
-            r1.push((it.next().unwrap(), it.next().unwrap()));
-        } else {
-            r2.push(Vec::from_iter(
-                l.split(",").map(|v| v.parse::<u32>().unwrap()),
-            ));
-        }
-    }
-    (r1, r2)
+type Rules = Vec<(u32, u32)>;
+type Updates = Vec<Vec<u32>>;
+
+pub fn parse(input: &str) -> Result<(Rules, Updates), parse::ParseError> {
+    let rule = separated_pair(uint(), tag("|"), uint());
+    let update = separated(uint(), ",");
+    parse::parse(input, sections(lines(rule), lines(update)))
 }
 
 fn get_position_map(v: &[u32]) -> HashMap<u32, usize> {
@@ -39,8 +34,8 @@ pub fn get_requirement_map(inp: &[(u32, u32)]) -> HashMap<u32, Vec<u32>> {
     m
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (pairs, list) = parse(input);
+pub fn part_one(input: &str) -> Result<u32, parse::ParseError> {
+    let (pairs, list) = parse(input)?;
     let position_maps = get_position_maps(&list);
     let requirements = get_requirement_map(&pairs);
     let mut mid_sum = 0;
@@ -52,7 +47,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         }
     }
 
-    Some(mid_sum)
+    Ok(mid_sum)
 }
 
 pub fn satisfied(
@@ -86,8 +81,8 @@ fn is_pure_satisfied(
     }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (pairs, list) = parse(input);
+pub fn part_two(input: &str) -> Result<u32, parse::ParseError> {
+    let (pairs, list) = parse(input)?;
     let position_maps = get_position_maps(&list);
     let requirements = get_requirement_map(&pairs);
     // use the algo from p1 to get incorrectly-ordered stuff
@@ -145,7 +140,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         mid_sum += updates[(updates.len() - 1) / 2];
     }
 
-    Some(mid_sum)
+    Ok(mid_sum)
 }
 
 #[cfg(test)]
@@ -155,12 +150,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
+        assert_eq!(result, Ok(143));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
+        assert_eq!(result, Ok(123));
     }
 }
@@ -1,120 +1,74 @@
+use advent_of_code::{
+    direction::Dir4,
+    grid::{Grid, Pos},
+};
 use rayon::prelude::*;
 
 advent_of_code::solution!(6);
 
 #[derive(PartialEq, Clone, Copy, Debug)]
-enum Pos {
+enum Tile {
     Air,
     Obstacle,
     Guard,
-    Visited(i8, i8),
+    Visited(Dir4),
 }
 
-fn turn_right(dir_row: &mut i8, dir_col: &mut i8) {
-    let help = *dir_col;
-    *dir_col = -(*dir_row);
-    *dir_row = help;
+fn parse(input: &str) -> Grid<Tile> {
+    Grid::parse(input, |c, _| {
+        if c == '#' {
+            Tile::Obstacle
+        } else if c == '.' {
+            Tile::Air
+        } else {
+            Tile::Guard
+        }
+    })
 }
 
-// part one, does not use visited, uses guard of Pos
+// part one, does not use visited, uses guard of Tile
 // mutate the field, position, return nr of tiles changed
-fn do_step(
-    field: &mut [Vec<Pos>],
-    row: &mut usize,
-    col: &mut usize,
-    dir_row: &mut i8,
-    dir_col: &mut i8,
-) -> Option<u32> {
-    let next_r = *row as i64 + *dir_row as i64;
-    let next_c = *col as i64 + *dir_col as i64;
-    if next_r < 0 || next_c < 0 || next_c as usize >= field.len() || next_r as usize >= field.len()
-    {
-        return None;
-    }
+fn do_step(field: &mut Grid<Tile>, pos: &mut Pos, dir: &mut Dir4) -> Option<u32> {
+    let next = pos.checked_add(*dir, field.bounds())?;
 
-    // Next Usize Row / Column
-    let nur = next_r as usize;
-    let nuc = next_c as usize;
-    if field[nur][nuc] == Pos::Obstacle {
-        turn_right(dir_row, dir_col);
+    if field[next] == Tile::Obstacle {
+        *dir = dir.turn_right();
         Some(0)
     } else {
-        let res = if field[nur][nuc] == Pos::Air { 1 } else { 0 };
-        field[nur][nuc] = Pos::Guard;
-        *row = nur;
-        *col = nuc;
+        let res = if field[next] == Tile::Air { 1 } else { 0 };
+        field[next] = Tile::Guard;
+        *pos = next;
         Some(res)
     }
 }
 
-// part one, does not use visited, uses guard of Pos
-// mutate the field, position, return nr of tiles changed
-fn do_step_2(
-    field: &mut [Vec<Pos>],
-    row: &mut usize,
-    col: &mut usize,
-    dir_row: &mut i8,
-    dir_col: &mut i8,
-) -> Option<bool> {
-    let next_r = *row as i64 + *dir_row as i64;
-    let next_c = *col as i64 + *dir_col as i64;
-    if next_r < 0 || next_c < 0 || next_c as usize >= field.len() || next_r as usize >= field.len()
-    {
+// part two, marks visited tiles with the direction they were entered in
+// mutate the field, position, return whether the guard is stuck in a loop once decided
+fn do_step_2(field: &mut Grid<Tile>, pos: &mut Pos, dir: &mut Dir4) -> Option<bool> {
+    let Some(next) = pos.checked_add(*dir, field.bounds()) else {
         return Some(false);
-    }
+    };
 
-    // Next Usize Row / Column
-    let nur = next_r as usize;
-    let nuc = next_c as usize;
-    let cur = &field[nur][nuc];
-
-    if let Pos::Visited(i, j) = cur {
-        if i == dir_row && j == dir_col {
-            return Some(true);
-        }
+    if field[next] == Tile::Visited(*dir) {
+        return Some(true);
     }
 
-    if field[nur][nuc] == Pos::Obstacle {
-        turn_right(dir_row, dir_col);
-        None
+    if field[next] == Tile::Obstacle {
+        *dir = dir.turn_right();
     } else {
-        field[nur][nuc] = Pos::Visited(*dir_row, *dir_col);
-        *row = nur;
-        *col = nuc;
-        None
+        field[next] = Tile::Visited(*dir);
+        *pos = next;
     }
+    None
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut parsed = input
-        .split_ascii_whitespace()
-        .map(|l| {
-            l.chars()
-                .map(|c| {
-                    if c == '#' {
-                        Pos::Obstacle
-                    } else if c == '.' {
-                        Pos::Air
-                    } else {
-                        Pos::Guard
-                    }
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-    let mut guard = (0, 0);
-    for i in 0..parsed.len() {
-        for j in 0..parsed.len() {
-            if parsed[i][j] == Pos::Guard {
-                guard = (i, j);
-            }
-        }
-    }
-    let (mut x, mut y) = guard;
-    let (mut dx, mut dy) = (-1, 0);
+    let mut field = parse(input);
+    let mut pos = Pos::from(field.find(&Tile::Guard)?);
+    let mut dir = Dir4::Up;
 
     let mut res = 1;
-    while let Some(n) = do_step(&mut parsed, &mut x, &mut y, &mut dx, &mut dy) {
+    while let Some(n) = do_step(&mut field, &mut pos, &mut dir) {
         res += n;
     }
 
@@ -122,59 +76,29 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let parsed = input
-        .split_ascii_whitespace()
-        .map(|l| {
-            l.chars()
-                .map(|c| {
-                    if c == '#' {
-                        Pos::Obstacle
-                    } else if c == '.' {
-                        Pos::Air
-                    } else {
-                        Pos::Guard
-                    }
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>();
-    let arc = std::sync::Arc::new(parsed);
-    let parsed = arc.clone();
-    let mut guard = (0, 0);
-    for i in 0..parsed.len() {
-        for j in 0..parsed.len() {
-            if parsed[i][j] == Pos::Guard {
-                guard = (i, j);
-            }
-        }
-    }
+    let field = parse(input);
+    let guard = Pos::from(field.find(&Tile::Guard)?);
 
     // 4s to 500ms (debug) (release thread::scope 55ms) (further improvement with rayon 35ms)
-    // for some reason removing the Arcs slows rayon to 50ms
-    let res = parsed
-        .iter()
-        .map(|_| arc.clone())
-        .collect::<Vec<_>>()
-        .par_iter()
-        .enumerate()
-        .map(|(i, parsed)| {
+    let res = (0..field.height())
+        .into_par_iter()
+        .map(|i| {
             let mut subtotal = 0;
-            for j in 0..parsed.len() {
-                let (mut x, mut y) = guard;
-                let (mut dx, mut dy) = (-1, 0);
+            for j in 0..field.width() {
+                let mut pos = guard;
+                let mut dir = Dir4::Up;
 
-                let mut working_cpy = Vec::from_iter(parsed.clone().iter().cloned());
-                if i != x || j != y {
+                let mut working_cpy = field.clone();
+                if Pos::new(i, j) != guard {
                     // one could also replace just the positions from the part one,
                     // which became marked and place an obstacle there
                     // but since this was fast enough and actually fast to implement
                     // i cant be bothered xd
-                    working_cpy[i][j] = Pos::Obstacle;
+                    working_cpy[(i, j)] = Tile::Obstacle;
                 }
 
                 loop {
-                    let res = do_step_2(&mut working_cpy, &mut x, &mut y, &mut dx, &mut dy);
-                    if let Some(b) = res {
+                    if let Some(b) = do_step_2(&mut working_cpy, &mut pos, &mut dir) {
                         subtotal += if b { 1 } else { 0 };
                         break;
                     }
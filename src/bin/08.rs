@@ -1,49 +1,43 @@
 use std::collections::{HashMap, HashSet};
 
-use advent_of_code::parse_field;
+use advent_of_code::grid::Grid;
 
 advent_of_code::solution!(8);
 
 pub fn get_antinodes(
     one: (usize, usize),
     two: (usize, usize),
-    size: usize,
-    iters: usize,
+    field: &Grid<char>,
+    iters: isize,
 ) -> Vec<(usize, usize)> {
-    let (r1, c1) = (one.0 as i32, one.1 as i32);
-    let (r2, c2) = (two.0 as i32, two.1 as i32);
-
-    let (dr, dc) = (r2 - r1, c2 - c1);
+    let (dr, dc) = (
+        two.0 as isize - one.0 as isize,
+        two.1 as isize - one.1 as isize,
+    );
 
     let mut res = vec![];
     for mul in 1..iters + 1 {
-        let candidate1 = (r1 - dr * mul as i32, c1 - dc * mul as i32);
-        let candidate2 = (r2 + dr * mul as i32, c2 + dc * mul as i32);
-        for cand in [candidate1, candidate2] {
-            if cand.0 >= 0 && cand.1 >= 0 && cand.0 < size as i32 && cand.1 < size as i32 {
-                res.push((cand.0 as usize, cand.1 as usize));
-            }
-        }
+        res.extend(field.offset(one, (-dr * mul, -dc * mul)));
+        res.extend(field.offset(two, (dr * mul, dc * mul)));
     }
     res
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
     let mut antena_map = HashMap::<char, Vec<(usize, usize)>>::new();
-    let size = parse_field(input, |c, position| {
+    let field = Grid::parse(input, |c, position| {
         if c.is_alphanumeric() {
             let entry = antena_map.entry(c).or_default();
             entry.push(position);
         }
         c
-    })
-    .len();
+    });
 
     let mut antinodes: HashSet<(usize, usize)> = HashSet::new();
     for v in antena_map.values() {
         for i in 0..v.len() {
             for j in i + 1..v.len() {
-                for an in get_antinodes(v[i], v[j], size, 1) {
+                for an in get_antinodes(v[i], v[j], &field, 1) {
                     antinodes.insert(an);
                 }
             }
@@ -55,21 +49,20 @@ pub fn part_one(input: &str) -> Option<usize> {
 
 pub fn part_two(input: &str) -> Option<usize> {
     let mut antena_map = HashMap::<char, Vec<(usize, usize)>>::new();
-    let size = parse_field(input, |c, position| {
+    let field = Grid::parse(input, |c, position| {
         if c.is_alphanumeric() {
             let entry = antena_map.entry(c).or_default();
             entry.push(position);
         }
         c
-    })
-    .len();
+    });
 
     let mut antinodes: HashSet<(usize, usize)> = HashSet::new();
     for v in antena_map.values() {
         for i in 0..v.len() {
             antinodes.insert(v[i]);
             for j in i + 1..v.len() {
-                for an in get_antinodes(v[i], v[j], size, 100) {
+                for an in get_antinodes(v[i], v[j], &field, 100) {
                     antinodes.insert(an);
                 }
             }
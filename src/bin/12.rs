@@ -1,55 +1,41 @@
 use std::{cmp::Ordering, collections::HashSet};
 
-use advent_of_code::parse_field;
+use advent_of_code::grid::{Grid, NEIGHBOURS_4};
 
 advent_of_code::solution!(12);
 
 pub fn dfs_same_chars(
     tgt: char,
-    (start_r, start_c): (usize, usize),
-    field: &mut [Vec<(char, bool)>],
+    pos: (usize, usize),
+    field: &mut Grid<(char, bool)>,
     acc: &mut HashSet<(usize, usize)>,
 ) {
-    let r: &mut (char, bool) = &mut field[start_r][start_c];
+    let r: &mut (char, bool) = &mut field[pos];
     if r.1 {
         return;
     }
 
     if r.0 == tgt {
-        acc.insert((start_r, start_c));
+        acc.insert(pos);
         r.1 = true;
     } else {
         return;
     }
-    let offsets = [(0, 1), (1, 0), (0, -1), (-1, 0)];
 
-    for (dr, dc) in offsets {
-        let next_r = start_r as i32 + dr;
-        let next_c = start_c as i32 + dc;
-
-        if next_r < 0 || next_c < 0 || next_r >= field.len() as i32 || next_c >= field.len() as i32
-        {
-            continue;
+    for delta in NEIGHBOURS_4 {
+        if let Some(next) = field.offset(pos, delta) {
+            dfs_same_chars(tgt, next, field, acc);
         }
-
-        dfs_same_chars(tgt, (next_r as usize, next_c as usize), field, acc);
     }
 }
 // just try every side of a field in an area - if not in the area -> got a piece of perimeter
-pub fn perimeter(area: &HashSet<(usize, usize)>, acc: &mut Vec<(i32, i32)>, field_limit: usize) {
-    let offsets = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-
+pub fn perimeter(area: &HashSet<(usize, usize)>, acc: &mut Vec<(isize, isize)>) {
     for (start_r, start_c) in area.iter() {
-        for (dr, dc) in offsets.iter() {
-            let next_r = *start_r as i32 + dr;
-            let next_c = *start_c as i32 + dc;
-
-            if next_r < -1
-                || next_c < -1
-                || next_r > field_limit as i32
-                || next_c > field_limit as i32
-                || area.contains(&(next_r as usize, next_c as usize))
-            {
+        for (dr, dc) in NEIGHBOURS_4 {
+            let next_r = *start_r as isize + dr;
+            let next_c = *start_c as isize + dc;
+
+            if area.contains(&(next_r as usize, next_c as usize)) {
                 continue;
             }
 
@@ -85,7 +71,7 @@ pub fn scan_x(r_coord: usize, area: &HashSet<(usize, usize)>) -> usize {
     // this trick allows us to count the leftmost and topmost sides (0-th row implies the element MUST be filtered IN)
     let on_row_over = filter_into_vec(
         &on_row,
-        |(r, c)| (*r == 0 || !area.contains(&(r - 1, *c))),
+        |(r, c)| *r == 0 || !area.contains(&(r - 1, *c)),
         sorter,
     );
     let on_row_below = filter_into_vec(&on_row, |(r, c)| !area.contains(&(r + 1, *c)), sorter);
@@ -115,7 +101,7 @@ pub fn scan_y(c_coord: usize, area: &HashSet<(usize, usize)>) -> usize {
     let sorter = |a: &&(usize, usize), b: &&(usize, usize)| a.0.cmp(&b.0);
     let on_col_left = filter_into_vec(
         &on_col,
-        |(r, c)| (*c == 0 || !area.contains(&(*r, c - 1))),
+        |(r, c)| *c == 0 || !area.contains(&(*r, c - 1)),
         sorter,
     );
     let on_col_right = filter_into_vec(&on_col, |(r, c)| !area.contains(&(*r, c + 1)), sorter);
@@ -137,31 +123,29 @@ pub fn scan_y(c_coord: usize, area: &HashSet<(usize, usize)>) -> usize {
     res
 }
 
-fn get_areas(field: &mut [Vec<(char, bool)>]) -> Vec<HashSet<(usize, usize)>> {
+fn get_areas(field: &mut Grid<(char, bool)>) -> Vec<HashSet<(usize, usize)>> {
     let mut areas: Vec<HashSet<(usize, usize)>> = vec![];
-    for row in 0..field.len() {
-        for col in 0..field.len() {
-            if field[row][col].1 {
-                continue;
-            }
-            let mut res = HashSet::new();
-            dfs_same_chars(field[row][col].0, (row, col), field, &mut res);
-            areas.push(res);
+    for pos in field.positions() {
+        if field[pos].1 {
+            continue;
         }
+        let mut res = HashSet::new();
+        dfs_same_chars(field[pos].0, pos, field, &mut res);
+        areas.push(res);
     }
     areas
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
     // parsing field for area lookup -> inserts a visited flag next to each character
-    let mut field = parse_field(input, |v, _| (v, false));
+    let mut field = Grid::parse(input, |v, _| (v, false));
     let areas = get_areas(&mut field);
 
     let mut result = 0;
-    let mut perimeters: Vec<Vec<(i32, i32)>> = vec![];
+    let mut perimeters: Vec<Vec<(isize, isize)>> = vec![];
     for area in areas.iter() {
         let mut res = Vec::new();
-        perimeter(area, &mut res, field.len());
+        perimeter(area, &mut res);
         result += area.len() * res.len();
         perimeters.push(res);
     }
@@ -169,17 +153,19 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut field = parse_field(input, |v, _| (v, false));
+    let mut field = Grid::parse(input, |v, _| (v, false));
     let areas = get_areas(&mut field);
 
     let mut result = 0;
     for area in areas.iter() {
         let mut sides = 0;
-        for x in 0..field.len() {
-            // the idea is to scan the area horizontally and vertically
-            // (needs sorting and detection of [non]consecutive ranges)
-            sides += scan_x(x, area);
-            sides += scan_y(x, area);
+        // the idea is to scan the area horizontally and vertically
+        // (needs sorting and detection of [non]consecutive ranges)
+        for row in 0..field.height() {
+            sides += scan_x(row, area);
+        }
+        for col in 0..field.width() {
+            sides += scan_y(col, area);
         }
         result += sides * area.len();
     }
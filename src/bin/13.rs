@@ -1,4 +1,6 @@
-advent_of_code::solution!(13);
+use advent_of_code::parse::{self, blocks, field, int, separated_pair, tag, PResult, ParseError};
+
+advent_of_code::solution!(13, try_parse = parse);
 
 // ! solves system:
 // ax * A + bx * B = res_x
@@ -37,53 +39,31 @@ pub fn solve_eqation(
     }
 }
 
-fn helper_extract(s: &str, delim: char) -> i64 {
-    s.split(delim)
-        .nth(1)
-        .unwrap()
-        .trim_end_matches(',')
-        .parse()
-        .unwrap()
-}
+type Pair = (i64, i64);
+type Machine = (Pair, Pair, Pair);
 
-fn get_num(s: &str) -> i64 {
-    if s.contains("+") {
-        helper_extract(s, '+')
-    } else if s.contains('=') {
-        helper_extract(s, '=')
-    } else {
-        panic!("invalid {}", s);
-    }
+// "X+94, Y+34" for buttons, "X=8400, Y=5400" for prizes
+fn coords<'a>(x: &'static str, y: &'static str) -> impl Fn(&'a str) -> PResult<'a, Pair> + Copy {
+    separated_pair(field(x, int()), tag(", "), field(y, int()))
 }
 
-fn part_with_shift(input: &str, shift: i64) -> i64 {
-    let nums = input
-        .split_ascii_whitespace()
-        .filter(|s| s.starts_with("X") || s.starts_with("Y"))
-        .collect::<Vec<_>>();
-
-    let buttons = nums
-        .iter()
-        .filter(|s| !s.starts_with("X=") && !s.starts_with("Y="))
-        .collect::<Vec<_>>();
-    let prizes = nums
-        .iter()
-        .filter(|s| s.starts_with("X=") || s.starts_with("Y="))
-        .collect::<Vec<_>>();
+fn parse(input: &str) -> Result<Vec<Machine>, ParseError> {
+    let button_a = field("Button A: ", coords("X+", "Y+"));
+    let button_b = field("Button B: ", coords("X+", "Y+"));
+    let prize = field("Prize: ", coords("X=", "Y="));
+    let machine = separated_pair(
+        button_a,
+        tag("\n"),
+        separated_pair(button_b, tag("\n"), prize),
+    );
+    let machines = parse::parse(input, blocks(machine))?;
+    Ok(machines.into_iter().map(|(a, (b, p))| (a, b, p)).collect())
+}
 
+fn part_with_shift(machines: &[Machine], shift: i64) -> i64 {
     let mut res = 0;
 
-    for prize_idx in 0..(prizes.len() / 2) {
-        let button_a_idx = prize_idx * 4;
-        let button_b_idx = button_a_idx + 2;
-        let (sax, say) = (buttons[button_a_idx], buttons[button_a_idx + 1]);
-        let (sbx, sby) = (buttons[button_b_idx], buttons[button_b_idx + 1]);
-        let (pr_x, pr_y) = (prizes[prize_idx * 2], prizes[prize_idx * 2 + 1]);
-
-        let (ax, ay) = (get_num(sax), get_num(say));
-        let (bx, by) = (get_num(sbx), get_num(sby));
-        let (res_x, res_y) = (get_num(pr_x), get_num(pr_y));
-
+    for &((ax, ay), (bx, by), (res_x, res_y)) in machines {
         if let Some((a_count, b_count)) =
             solve_eqation(ax, bx, res_x + shift, ay, by, res_y + shift)
         {
@@ -93,12 +73,12 @@ fn part_with_shift(input: &str, shift: i64) -> i64 {
     res
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
-    Some(part_with_shift(input, 0))
+pub fn part_one(machines: &[Machine]) -> Option<i64> {
+    Some(part_with_shift(machines, 0))
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    Some(part_with_shift(input, 10000000000000))
+pub fn part_two(machines: &[Machine]) -> Option<i64> {
+    Some(part_with_shift(machines, 10000000000000))
 }
 
 #[cfg(test)]
@@ -107,13 +87,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let result = part_one(&parse(&input).unwrap());
         assert_eq!(result, Some(480));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let result = part_two(&parse(&input).unwrap());
         assert_eq!(result, None);
     }
 }
@@ -1,4 +1,15 @@
-advent_of_code::solution!(14);
+advent_of_code::solution!(14, params = Params);
+
+advent_of_code::params! {
+    struct Params {
+        /// Width of the space the robots move in.
+        bound_x: i64 = (11, 101),
+        /// Height of the space the robots move in.
+        bound_y: i64 = (7, 103),
+    }
+}
+
+use advent_of_code::parse::{self, field, int, lines, separated_pair, tag, ParseError};
 
 type Pair = (i64, i64);
 
@@ -63,32 +74,17 @@ fn print_robots(robots: &mut [(Pair, Pair)], bound_x: i64, bound_y: i64) {
     }
 }
 
-fn num_pair(input: &str, prefix: &str) -> (i64, i64) {
-    let mut split = input.split(',');
-    (
-        split
-            .next()
-            .unwrap()
-            .trim_start_matches(prefix)
-            .parse::<i64>()
-            .unwrap(),
-        split.next().unwrap().parse::<i64>().unwrap(),
-    )
+// "p=0,4 v=3,-3" per line
+fn parse(input: &str) -> Result<Vec<(Pair, Pair)>, ParseError> {
+    let pair = separated_pair(int(), tag(","), int());
+    let robot = separated_pair(field("p=", pair), tag(" "), field("v=", pair));
+    parse::parse(input, lines(robot))
 }
 
 pub fn part_iters(input: &str, iters: usize, print: bool) -> usize {
-    let mut robots = input
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|x| {
-            let mut split = x.split_ascii_whitespace();
-            (split.next().unwrap(), split.next().unwrap())
-        })
-        .map(|(pos, sp)| (num_pair(pos, "p="), num_pair(sp, "v=")))
-        .collect::<Vec<_>>();
-
-    let bound_x = 101; //11;
-    let bound_y = 103; //7;
+    let mut robots = parse(input).unwrap();
+
+    let Params { bound_x, bound_y } = params().clone();
 
     for i in 0..iters {
         step_second(&mut robots, bound_x, bound_y);
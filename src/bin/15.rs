@@ -1,26 +1,8 @@
 use std::mem;
 
-advent_of_code::solution!(15);
-
-#[derive(Clone, Copy)]
-enum Insn {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+use advent_of_code::{direction::Dir4, grid::Grid};
 
-impl From<char> for Insn {
-    fn from(value: char) -> Self {
-        match value {
-            'v' => Insn::Down,
-            '>' => Insn::Right,
-            '<' => Insn::Left,
-            '^' => Insn::Up,
-            _ => panic!("Unexpected"),
-        }
-    }
-}
+advent_of_code::solution!(15);
 
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 enum MapObj {
@@ -31,8 +13,7 @@ enum MapObj {
     ObstacleOpen,
     ObstacleClose,
 }
-type FieldV = Vec<Vec<MapObj>>;
-type FieldS = [Vec<MapObj>];
+type Field = Grid<MapObj>;
 
 impl From<char> for MapObj {
     fn from(value: char) -> Self {
@@ -47,146 +28,96 @@ impl From<char> for MapObj {
 }
 // PART 1 SPECIFICS ------------------------------------------------------------
 // looks in 1 direction and searches for 1 free space (allows the shift)
-fn has_empty_in_dir(
-    row_shift: i32,
-    col_shift: i32,
-    fld: &mut FieldS,
-    from_row: usize,
-    from_col: usize,
-) -> bool {
-    let mut now_row = from_row as i32 + row_shift;
-    let mut now_col = from_col as i32 + col_shift;
-    let limit = fld.len() as i32;
-    let out_of_bounds =
-        |x: i32, y: i32| x < 0 || x >= limit || y >= fld[x as usize].len() as i32 || y < 0;
-    // println!("Try shift {row_shift} {col_shift} @ {from_row} {from_col}");
-    while !out_of_bounds(now_row, now_col) {
-        // println!("{} {}", now_row, now_col);
-        let r = now_row as usize;
-        let c = now_col as usize;
-        let curr = fld[r][c];
-        if curr == MapObj::Empty {
-            // println!("Found at {r} {c}");
-            return true;
-        } else if curr == MapObj::Wall {
-            return false;
+fn has_empty_in_dir(dir: Dir4, fld: &Field, from: (usize, usize)) -> bool {
+    let mut now = from;
+    while let Some(next) = fld.offset(now, dir) {
+        match fld[next] {
+            MapObj::Empty => return true,
+            MapObj::Wall => return false,
+            _ => now = next,
         }
-
-        now_row += row_shift;
-        now_col += col_shift;
     }
     false
 }
 
-fn shift_col(fld: &mut FieldS, (rob_row, rob_col): (usize, usize), shift: i32) {
+fn shift_col(fld: &mut Field, (rob_row, rob_col): (usize, usize), shift: isize) {
     let mut prev_val = MapObj::Empty;
-    let mut next = rob_row as i32;
-    while fld[next as usize][rob_col] != MapObj::Empty {
-        mem::swap(&mut prev_val, &mut fld[next as usize][rob_col]);
+    let mut next = rob_row as isize;
+    while fld[(next as usize, rob_col)] != MapObj::Empty {
+        mem::swap(&mut prev_val, &mut fld[(next as usize, rob_col)]);
         next += shift;
     }
-    fld[next as usize][rob_col] = prev_val;
+    fld[(next as usize, rob_col)] = prev_val;
 }
 
-fn shift_row(fld: &mut FieldS, (rob_row, rob_col): (usize, usize), shift: i32) {
+fn shift_row(fld: &mut Field, (rob_row, rob_col): (usize, usize), shift: isize) {
     let mut prev_val = MapObj::Empty;
-    let mut next = rob_col as i32;
-    while fld[rob_row][next as usize] != MapObj::Empty {
-        // println!("Next: {next} ({}) at {rob_row}", next as usize);
-        mem::swap(&mut prev_val, &mut fld[rob_row][next as usize]);
+    let mut next = rob_col as isize;
+    while fld[(rob_row, next as usize)] != MapObj::Empty {
+        mem::swap(&mut prev_val, &mut fld[(rob_row, next as usize)]);
         next += shift;
     }
-    fld[rob_row][next as usize] = prev_val;
+    fld[(rob_row, next as usize)] = prev_val;
 }
 
-fn step(
-    fld: &mut FieldV,
-    (rob_row, rob_col): (usize, usize),
-    insn: Insn,
-) -> Option<(usize, usize)> {
-    let (dir_row, dir_col) = match insn {
-        Insn::Down => (1, 0),
-        Insn::Up => (-1, 0),
-        Insn::Left => (0, -1),
-        Insn::Right => (0, 1),
-    };
+fn step(fld: &mut Field, (rob_row, rob_col): (usize, usize), insn: Dir4) -> Option<(usize, usize)> {
+    let (dir_row, dir_col) = insn.offset();
     // check if there is space for the very last box in the row
     // (or just the robot)
-    if !has_empty_in_dir(dir_row, dir_col, fld, rob_row, rob_col) {
+    if !has_empty_in_dir(insn, fld, (rob_row, rob_col)) {
         return None;
     }
-    // println!("OK to shift {rob_row} {rob_col} by {dir_row} {dir_col}");
     // perform shifting
-    if dir_col == 0 {
+    if insn.is_vertical() {
         shift_col(fld, (rob_row, rob_col), dir_row);
     } else {
         shift_row(fld, (rob_row, rob_col), dir_col);
     }
 
-    Some((
-        (rob_row as i32 + dir_row) as usize,
-        (rob_col as i32 + dir_col) as usize,
-    ))
+    fld.offset((rob_row, rob_col), insn)
 }
 // PART 1 SPECIFICS END --------------------------------------------------------
 
-fn parser(input: &str) -> (Vec<Vec<MapObj>>, Vec<Insn>) {
+fn parser(input: &str) -> (Field, Vec<Dir4>) {
     let line_iter = input.lines();
     let line_iter2 = line_iter.clone();
     let lines_map = line_iter.take_while(|l| !l.is_empty());
     let n = lines_map.clone().count();
     let insn_lines = line_iter2.skip(n + 1).collect::<String>();
-    let instructions = insn_lines.trim().chars().map(Insn::from).collect();
-
-    let field = lines_map
-        .map(|l| l.chars().map(MapObj::from).collect())
+    let instructions = insn_lines
+        .trim()
+        .chars()
+        .map(|c| Dir4::try_from(c).unwrap())
         .collect();
+
+    let map = lines_map.collect::<Vec<_>>().join("\n");
+    let field = Grid::parse(&map, |c, _| MapObj::from(c));
     (field, instructions)
 }
 
 fn part_driver<T>(
-    instructions: &[Insn],
-    field: &mut FieldV,
+    instructions: &[Dir4],
+    field: &mut Field,
     step_fn: &mut T,
     score_match: MapObj,
 ) -> usize
 where
-    T: FnMut(&mut FieldV, (usize, usize), Insn) -> Option<(usize, usize)>,
+    T: FnMut(&mut Field, (usize, usize), Dir4) -> Option<(usize, usize)>,
 {
     // find the robot
-    let mut robot_pos = None;
-    for row in 0..field.len() {
-        for col in 0..field[row].len() {
-            if field[row][col] == MapObj::Robot {
-                assert!(robot_pos.is_none(), "bug: duplicate robot position");
-                robot_pos = Some((row, col));
-            }
-        }
-    }
-    // print_field(&field);
+    let mut robot_pos = field.find(&MapObj::Robot).unwrap();
     // perform steps
-    let (mut r, mut c) = robot_pos.unwrap();
     for insn in instructions {
-        if let Some((nr, nc)) = step_fn(field, (r, c), *insn) {
-            // println!("Shifted to {nr} {nc}");
-            r = nr;
-            c = nc;
-        } else {
-            // println!("NOOP");
+        if let Some(next) = step_fn(field, robot_pos, *insn) {
+            robot_pos = next;
         }
     }
-    // print_field(&field);
     // calculate score
-    let mut res = 0;
-    for row in 0..field.len() {
-        for col in 0..field[row].len() {
-            if field[row][col] == score_match {
-                res += row * 100 + col;
-            }
-        }
-    }
-
-    res
+    field
+        .iter()
+        .filter(|(_, obj)| **obj == score_match)
+        .map(|((row, col), _)| row * 100 + col)
+        .sum()
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -199,13 +130,11 @@ pub fn part_one(input: &str) -> Option<usize> {
     ))
 }
 
-fn shift_cols_2(fld: &mut FieldS, (from_r, from_c): (usize, usize), shift: i32) -> bool {
-    let nextr = from_r as i32 + shift;
-    if nextr < 0 || nextr as usize >= fld.len() {
+fn shift_cols_2(fld: &mut Field, (from_r, from_c): (usize, usize), shift: isize) -> bool {
+    let Some((nextr, _)) = fld.offset((from_r, from_c), (shift, 0)) else {
         return false;
-    }
-    let nextr = nextr as usize;
-    let next_obj = fld[nextr][from_c];
+    };
+    let next_obj = fld[(nextr, from_c)];
     let moved = match next_obj {
         MapObj::Robot | MapObj::Obstacle => panic!("wtf {:?}", next_obj),
         MapObj::Empty => true,
@@ -221,29 +150,23 @@ fn shift_cols_2(fld: &mut FieldS, (from_r, from_c): (usize, usize), shift: i32)
     };
 
     if moved {
-        fld[nextr][from_c] = fld[from_r][from_c];
-        fld[from_r][from_c] = MapObj::Empty;
+        fld.swap((nextr, from_c), (from_r, from_c));
     }
 
     moved
 }
 
 fn step_2(
-    fld: &mut FieldV,
+    fld: &mut Field,
     (rob_row, rob_col): (usize, usize),
-    insn: Insn,
+    insn: Dir4,
 ) -> Option<(usize, usize)> {
-    let (dir_row, dir_col) = match insn {
-        Insn::Down => (1, 0),
-        Insn::Up => (-1, 0),
-        Insn::Left => (0, -1),
-        Insn::Right => (0, 1),
-    };
+    let (dir_row, dir_col) = insn.offset();
 
-    if dir_row == 0 {
+    if !insn.is_vertical() {
         // check if there is space for the very last box in the row
         // (or just the robot)
-        if !has_empty_in_dir(dir_row, dir_col, fld, rob_row, rob_col) {
+        if !has_empty_in_dir(insn, fld, (rob_row, rob_col)) {
             return None;
         }
         shift_row(fld, (rob_row, rob_col), dir_col);
@@ -254,37 +177,20 @@ fn step_2(
             return None;
         }
     }
-    Some((
-        (rob_row as i32 + dir_row) as usize,
-        (rob_col as i32 + dir_col) as usize,
-    ))
+    fld.offset((rob_row, rob_col), insn)
 }
 
 // part 1 input -> part 2 input
-fn p2_enlarge(map: &FieldS) -> FieldV {
-    let mut res = Vec::with_capacity(map.len());
-    for r in map {
-        let mut nr = Vec::with_capacity(map.len() * 2);
-        for c in r {
-            match c {
-                MapObj::Robot => {
-                    nr.push(*c);
-                    nr.push(MapObj::Empty);
-                }
-                MapObj::Obstacle => {
-                    nr.push(MapObj::ObstacleOpen);
-                    nr.push(MapObj::ObstacleClose);
-                }
-                x => {
-                    nr.push(*x);
-                    nr.push(*x);
-                }
-            };
-        }
-        res.push(nr);
-    }
-
-    res
+fn p2_enlarge(map: &Field) -> Field {
+    let cells = map
+        .iter()
+        .flat_map(|(_, c)| match c {
+            MapObj::Robot => [MapObj::Robot, MapObj::Empty],
+            MapObj::Obstacle => [MapObj::ObstacleOpen, MapObj::ObstacleClose],
+            x => [*x, *x],
+        })
+        .collect();
+    Grid::from_vec(map.width() * 2, cells)
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
@@ -299,23 +205,6 @@ pub fn part_two(input: &str) -> Option<usize> {
     ))
 }
 
-fn print_field(fld: &FieldS) {
-    for r in fld {
-        for c in r {
-            let chr = match c {
-                MapObj::Empty => '.',
-                MapObj::Robot => '@',
-                MapObj::Obstacle => 'O',
-                MapObj::Wall => '#',
-                MapObj::ObstacleOpen => '[',
-                MapObj::ObstacleClose => ']',
-            };
-            print!("{}", chr);
-        }
-        println!();
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
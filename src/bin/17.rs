@@ -1,3 +1,4 @@
+use advent_of_code::parse::{self, field, sections, separated, separated_pair, tag, uint};
 use pathfinding::num_traits::pow;
 
 advent_of_code::solution!(17);
@@ -53,33 +54,23 @@ impl From<(u8, u8)> for Insn {
 }
 
 fn parse_init(input: &str) -> (RegVal, RegVal, RegVal, Vec<Insn>, Vec<u8>) {
-    if let [l1, l2, l3, _, lp] = input.lines().collect::<Vec<_>>().as_slice() {
-        let get_reg_num = |i: &str| i.split(": ").nth(1).unwrap().parse::<RegVal>().unwrap();
-
-        let numbers = lp
-            .split(": ")
-            .nth(1)
-            .unwrap()
-            .split(",")
-            .map(|v| v.parse::<u8>().unwrap())
-            .collect::<Vec<_>>();
-        let mut insn = vec![];
-        for i in 0..numbers.len() {
-            if i % 2 == 0 {
-                insn.push(Insn::from((numbers[i], numbers[i + 1])));
-            }
-        }
-
-        (
-            get_reg_num(l1),
-            get_reg_num(l2),
-            get_reg_num(l3),
-            insn,
-            numbers,
-        )
-    } else {
-        panic!("xd {:?}", input.lines().collect::<Vec<_>>().as_slice());
-    }
+    let registers = separated_pair(
+        field("Register A: ", uint()),
+        tag("\n"),
+        separated_pair(
+            field("Register B: ", uint()),
+            tag("\n"),
+            field("Register C: ", uint()),
+        ),
+    );
+    let program = field("Program: ", separated(uint::<u8>(), ","));
+    let ((a, (b, c)), numbers) = parse::parse(input, sections(registers, program)).unwrap();
+
+    let insn = numbers
+        .chunks(2)
+        .map(|pair| Insn::from((pair[0], pair[1])))
+        .collect();
+    (a, b, c, insn, numbers)
 }
 
 struct Machine {
@@ -145,23 +136,6 @@ impl Machine {
         }
         true
     }
-    // used for bruteforce attempt xd
-    pub fn inspect(&self, expected: &[u8]) -> bool {
-        for (i, v) in self.results.iter().enumerate() {
-            if i < expected.len() && *v != expected[i] {
-                return false;
-            }
-        }
-        true
-    }
-
-    pub fn reset(&mut self, ip: usize, a: u64, b: u64, c: u64) {
-        self.ip = ip;
-        self.a = a;
-        self.b = b;
-        self.c = c;
-        self.results.clear();
-    }
 }
 
 pub fn part_one(input: &str) -> Option<String> {
@@ -232,30 +206,6 @@ fn fits(field: &NumField, idx: usize, bits: &Bits) -> bool {
     true
 }
 
-// usless but created as a preparation (gets all possibilies that fit)
-fn possibilities(field: &NumField, idx: usize) -> Vec<Bits> {
-    let mut result: Vec<Bits> = vec![[EMPTY; 3]];
-    for i in idx..(idx + 3).min(field.len()) {
-        let real_idx_field = field.len() - i - 1;
-        let real_idx_bits = 3 + idx - i - 1;
-        if field[real_idx_field] == EMPTY {
-            let mut other = vec![];
-            for x in &mut result {
-                let mut cpy = *x;
-                x[real_idx_bits] = 0;
-                cpy[real_idx_bits] = 1;
-                other.push(cpy);
-            }
-            result.append(&mut other);
-        } else {
-            for x in &mut result {
-                x[real_idx_bits] = field[real_idx_field];
-            }
-        }
-    }
-    result
-}
-
 fn possibilities_all(field: &NumField) -> Vec<NumField> {
     let mut result: Vec<NumField> = vec![[EMPTY; 64]];
     for i in 0..field.len() {
@@ -410,7 +360,6 @@ pub fn part_two(input: &str) -> Option<u64> {
     //     value[62] = EMPTY;
     //     value[61] = 1;
     //     println!("{}", fits(&value, 2, &[0, 1, 1]));
-    //     println!("Pos:\n{:?}", possibilities(&value, 2));
     //     merge(&mut value, 50, [0, 1, 1]);
     //     println!("{:?}", value);
     // }
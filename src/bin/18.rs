@@ -1,47 +1,37 @@
-use std::collections::{HashMap, HashSet};
-
-use pathfinding::directed::dijkstra;
+use advent_of_code::{graph, grid::Grid};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
-advent_of_code::solution!(18);
-
-type NodeType = (usize, usize);
+advent_of_code::solution!(18, params = Params);
 
-fn construct_successors(
-    obstacles: &[NodeType],
-    bound_x: usize,
-    bound_y: usize,
-    obst_limit: usize,
-) -> HashMap<NodeType, Vec<(NodeType, usize)>> {
-    let obstacles = obstacles.iter().take(obst_limit).collect::<HashSet<_>>();
-    let mut successors = HashMap::new();
+advent_of_code::params! {
+    struct Params {
+        /// Width and height of the memory space.
+        bound: usize = (7, 71),
+        /// Number of bytes that have fallen after the first kilobyte.
+        bytes: usize = (12, 1024),
+    }
+}
 
-    for y in 0..bound_y {
-        for x in 0..bound_x {
-            let v = if let std::collections::hash_map::Entry::Vacant(e) = successors.entry((x, y)) {
-                let ety: Vec<(NodeType, usize)> = vec![];
-                e.insert(ety);
-                successors.get_mut(&(x, y)).unwrap()
-            } else {
-                successors.get_mut(&(x, y)).unwrap()
-            };
-            for candidate in [(-1, 0), (1, 0), (0, 1), (0, -1)] {
-                let cx = x as i32 + candidate.0;
-                let cy = y as i32 + candidate.1;
-                if cx < 0 || cy < 0 || cx >= bound_x as i32 || cy >= bound_y as i32 {
-                    continue;
-                }
-                let usize_cand = (cx as usize, cy as usize);
-                if obstacles.contains(&usize_cand) {
-                    continue;
-                }
+type NodeType = (usize, usize);
 
-                v.push(((cx as usize, cy as usize), 1));
-            }
-        }
+fn construct_field(obstacles: &[NodeType], bound: usize, obst_limit: usize) -> Grid<bool> {
+    // the grid is indexed by (row, col), i.e. (y, x)
+    let mut blocked = Grid::new(bound, bound, false);
+    for &(x, y) in obstacles.iter().take(obst_limit) {
+        blocked[(y, x)] = true;
     }
+    blocked
+}
 
-    successors
+// number of steps from the top left to the bottom right corner
+fn shortest_path(blocked: &Grid<bool>) -> Option<usize> {
+    let end = (blocked.height() - 1, blocked.width() - 1);
+    let path = graph::bfs(
+        &(0, 0),
+        |&pos| blocked.neighbours_4(pos).filter(|next| !blocked[*next]),
+        |&pos| pos == end,
+    )?;
+    Some(path.len() - 1)
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -50,19 +40,8 @@ pub fn part_one(input: &str) -> Option<usize> {
         .map(|ln| ln.split(",").map(|v| v.parse::<usize>().unwrap()))
         .map(|mut v| (v.next().unwrap(), v.next().unwrap()))
         .collect::<Vec<_>>();
-    let bound = 7;
-    let end = (bound - 1, bound - 1);
-    let succ = construct_successors(&positions, bound, bound, 12);
-    let res = dijkstra::dijkstra(
-        &(0, 0),
-        |&v| (*(succ.get(&v).unwrap_or(&vec![]))).to_vec(),
-        |&(x, y)| end.0 == x && end.1 == y,
-    );
-    if let Some((_, price)) = res {
-        Some(price)
-    } else {
-        None
-    }
+    let Params { bound, bytes } = params().clone();
+    shortest_path(&construct_field(&positions, bound, bytes))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
@@ -71,18 +50,11 @@ pub fn part_two(input: &str) -> Option<usize> {
         .map(|ln| ln.split(",").map(|v| v.parse::<usize>().unwrap()))
         .map(|mut v| (v.next().unwrap(), v.next().unwrap()))
         .collect::<Vec<_>>();
-    let bound = 71;
-    let end = (bound - 1, bound - 1);
-    let bytes = 1024;
+    let Params { bound, bytes } = params().clone();
     let res = (bytes..positions.len())
         .into_par_iter()
         .find_map_first(|idx| {
-            let succ = construct_successors(&positions, bound, bound, idx);
-            let res = dijkstra::dijkstra(
-                &(0, 0),
-                |&v| (*(succ.get(&v).unwrap_or(&vec![]))).to_vec(),
-                |&(x, y)| end.0 == x && end.1 == y,
-            );
+            let res = shortest_path(&construct_field(&positions, bound, idx));
             if res.is_none() {
                 Some(positions[idx - 1])
             } else {
@@ -1,21 +1,29 @@
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, params = Params);
+
+advent_of_code::params! {
+    struct Params {
+        /// Minimum number of picoseconds a cheat needs to save in part one.
+        part_one_min_saving: u32 = (1, 100),
+        /// Minimum number of picoseconds a cheat needs to save in part two.
+        part_two_min_saving: u32 = (50, 100),
+    }
+}
 
-use std::{
-    collections::HashMap,
-    sync::{atomic, Arc},
-};
+use std::collections::HashMap;
 
-use advent_of_code::parse_field;
-use pathfinding::directed::*;
+use advent_of_code::{
+    graph,
+    grid::{Grid, NEIGHBOURS_4},
+};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 type Position = (usize, usize);
 type SuccessorMap = HashMap<Position, Vec<(Position, u32)>>;
 
-fn construct_field(input: &str) -> (Vec<Vec<char>>, Option<Position>, Option<Position>) {
+fn construct_field(input: &str) -> (Grid<char>, Option<Position>, Option<Position>) {
     let mut start = None;
     let mut end = None;
-    let fld = parse_field(input, |v, (r, c)| {
+    let fld = Grid::parse(input, |v, (r, c)| {
         if v == 'S' {
             start = Some((r, c));
             'S'
@@ -29,33 +37,27 @@ fn construct_field(input: &str) -> (Vec<Vec<char>>, Option<Position>, Option<Pos
     (fld, start, end)
 }
 
-fn in_field((r, c): &(i32, i32), field: &[Vec<char>]) -> bool {
-    !(*r < 0 || *r as usize >= field.len() || *c < 0 || *c as usize >= field[*r as usize].len())
-}
-
 // normal SuccessorMap construction without cheating
-fn construct_successors(input: &str, field: &[Vec<char>]) -> SuccessorMap {
+fn construct_successors(field: &Grid<char>) -> SuccessorMap {
     let mut successors: HashMap<Position, Vec<(Position, u32)>> = HashMap::new();
-    let _ = parse_field(input, |_, field_pos| {
-        if field[field_pos.0][field_pos.1] == '#' {
-            return;
+    for (field_pos, v) in field.iter() {
+        if *v == '#' {
+            continue;
         }
         let mut successor_list = vec![];
-        let from_node = field_pos;
-        for dir in [(-1, 0), (1, 0), (0, 1), (0, -1)] {
-            let upcoming_pos = (field_pos.0 as i32 + dir.0, field_pos.1 as i32 + dir.1);
-            if !in_field(&upcoming_pos, field) {
+        for dir in NEIGHBOURS_4 {
+            let Some(upcoming_pos) = field.offset(field_pos, dir) else {
                 continue;
-            }
+            };
             // I forgot about this skip and it cost me additional 80% time in part 2
             // and ~33% in part 1
-            if field[upcoming_pos.0 as usize][upcoming_pos.1 as usize] == '#' {
+            if field[upcoming_pos] == '#' {
                 continue;
             }
-            successor_list.push(((upcoming_pos.0 as usize, upcoming_pos.1 as usize), 1u32));
+            successor_list.push((upcoming_pos, 1u32));
         }
-        successors.insert(from_node, successor_list);
-    });
+        successors.insert(field_pos, successor_list);
+    }
     successors
 }
 
@@ -63,15 +65,13 @@ fn construct_successors(input: &str, field: &[Vec<char>]) -> SuccessorMap {
 // otherwise the returned SuccessorMap is normal
 fn construct_successors_manhattan(
     manhattan_target: (usize, usize),
-    input: &str,
-    field: &[Vec<char>],
+    field: &Grid<char>,
     manhattan_budget: u32,
 ) -> SuccessorMap {
     let mut options = HashMap::new();
     get_manhattan_options(&manhattan_target, field, manhattan_budget, &mut options);
     let options = options.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
-    let mut successors: HashMap<Position, Vec<(Position, u32)>> =
-        construct_successors(input, field);
+    let mut successors: HashMap<Position, Vec<(Position, u32)>> = construct_successors(field);
     let mut successor_map: HashMap<(usize, usize), u32> = HashMap::new();
     for (pos, cost) in options {
         if pos.0 != manhattan_target.0 || pos.1 != manhattan_target.1 {
@@ -93,7 +93,7 @@ fn construct_successors_manhattan(
 
 fn get_manhattan_options(
     (or, oc): &Position,
-    field: &[Vec<char>],
+    field: &Grid<char>,
     budget: u32,
     accumulator: &mut HashMap<Position, u32>,
 ) {
@@ -106,12 +106,12 @@ fn get_manhattan_options(
             if dc == 0 && dr == 0 {
                 continue;
             }
-            let (nr, nc) = (*or as i32 + dr, *oc as i32 + dc);
-            if !in_field(&(nr, nc), field) {
+            let (nr, nc) = (*or as isize + dr as isize, *oc as isize + dc as isize);
+            let Some(cell) = field.get_signed((nr, nc)) else {
                 continue;
-            }
+            };
             let (cand_r, cand_c) = (nr as usize, nc as usize);
-            if field[cand_r][cand_c] != '#' {
+            if *cell != '#' {
                 let cost = (dr.abs() + dc.abs()) as u32;
                 accumulator
                     .entry((cand_r, cand_c))
@@ -149,12 +149,12 @@ pub fn solver<T: Fn(&u32, &u32) -> bool + Send + Sync>(
     filter_fn: T,
 ) -> Option<usize> {
     let (field, start, end) = construct_field(input);
-    let successors = construct_successors(input, &field);
+    let successors = construct_successors(&field);
 
     assert!(start.is_some() && end.is_some());
-    let (nocheat_path, nocheat_cost) = dijkstra::dijkstra(
+    let (nocheat_path, nocheat_cost) = graph::dijkstra(
         &start.unwrap(),
-        |&v| (*(successors.get(&v).unwrap_or(&vec![]))).to_vec(),
+        |v| graph::adjacent(&successors, v),
         |&(r, c)| end.unwrap().0 == r && end.unwrap().1 == c,
     )
     .unwrap();
@@ -171,12 +171,12 @@ pub fn solver<T: Fn(&u32, &u32) -> bool + Send + Sync>(
             // );
             let loc_field = field.clone();
             let mut loc_successors =
-                construct_successors_manhattan(*manhattan_target, input, &loc_field, picos);
+                construct_successors_manhattan(*manhattan_target, &loc_field, picos);
             let mut acc_res = 0;
 
-            while let Some((path, cost)) = dijkstra::dijkstra(
+            while let Some((path, cost)) = graph::dijkstra(
                 &start.unwrap(),
-                |&v| (*(loc_successors.get(&v).unwrap_or(&vec![]))).to_vec(),
+                |v| graph::adjacent(&loc_successors, v),
                 |&(r, c)| end.unwrap().0 == r && end.unwrap().1 == c,
             ) {
                 if !filter_fn(&cost, &nocheat_cost) {
@@ -196,16 +196,14 @@ pub fn solver<T: Fn(&u32, &u32) -> bool + Send + Sync>(
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let filter_fn = |cost: &u32, nocheat_cost: &u32| *cost < *nocheat_cost;
-    // comment out the line below for EXAMPLE input
-    let filter_fn = |cost: &u32, nocheat_cost: &u32| *cost + 100 <= *nocheat_cost;
+    let min_saving = params().part_one_min_saving;
+    let filter_fn = |cost: &u32, nocheat_cost: &u32| *cost + min_saving <= *nocheat_cost;
     solver(input, 2, filter_fn)
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let filter_fn = |cost: &u32, nocheat_cost: &u32| *cost + 50 <= *nocheat_cost;
-    // comment out the line below for EXAMPLE input
-    let filter_fn = |cost: &u32, nocheat_cost: &u32| *cost + 100 <= *nocheat_cost;
+    let min_saving = params().part_two_min_saving;
+    let filter_fn = |cost: &u32, nocheat_cost: &u32| *cost + min_saving <= *nocheat_cost;
     solver(input, 20, filter_fn)
 }
 
@@ -1,3 +1,4 @@
+use advent_of_code::direction::Dir4;
 use rayon::iter::{IntoParallelRefIterator, ParallelBridge, ParallelIterator};
 use std::{
     collections::{HashMap, HashSet},
@@ -39,20 +40,17 @@ impl KeypadKey for NumericKey {
 
 #[derive(Hash, Debug, Eq, PartialEq, Clone, Copy)]
 enum PushInsn {
-    L,
-    R,
-    U,
-    D,
+    Move(Dir4),
     A,
 }
 
 impl KeypadKey for PushInsn {
     fn get_coordinates(&self) -> Position {
         match self {
-            PushInsn::L => (0, 0),
-            PushInsn::R => (0, 2),
-            PushInsn::U => (1, 1),
-            PushInsn::D => (0, 1),
+            PushInsn::Move(Dir4::Left) => (0, 0),
+            PushInsn::Move(Dir4::Right) => (0, 2),
+            PushInsn::Move(Dir4::Up) => (1, 1),
+            PushInsn::Move(Dir4::Down) => (0, 1),
             PushInsn::A => (1, 2),
         }
     }
@@ -64,7 +62,13 @@ impl EnumAll for PushInsn {
         Self: std::marker::Sized,
     {
         // swapping A to the back makes the runtime of part 1 2-3x slower
-        vec![Self::A, Self::L, Self::R, Self::U, Self::D]
+        vec![
+            Self::A,
+            Self::Move(Dir4::Left),
+            Self::Move(Dir4::Right),
+            Self::Move(Dir4::Up),
+            Self::Move(Dir4::Down),
+        ]
     }
 }
 
@@ -102,11 +106,12 @@ fn get_all_moves<T: EnumAll + Hash + Eq + PartialEq + Sized + KeypadKey + Copy>(
             .map(|(k, (r, c))| {
                 (
                     *k,
+                    // keypad rows are counted from the bottom
                     match (r as i32 - from.0 as i32, c as i32 - from.1 as i32) {
-                        (1, 0) => Some(PushInsn::U),
-                        (-1, 0) => Some(PushInsn::D),
-                        (0, 1) => Some(PushInsn::R),
-                        (0, -1) => Some(PushInsn::L),
+                        (1, 0) => Some(PushInsn::Move(Dir4::Up)),
+                        (-1, 0) => Some(PushInsn::Move(Dir4::Down)),
+                        (0, 1) => Some(PushInsn::Move(Dir4::Right)),
+                        (0, -1) => Some(PushInsn::Move(Dir4::Left)),
                         _ => None,
                     },
                 )
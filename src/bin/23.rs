@@ -7,7 +7,7 @@ advent_of_code::solution!(23);
 type SuccMapVec<'a> = HashMap<&'a str, Vec<&'a str>>;
 type SuccMapSet<'a> = HashMap<&'a str, HashSet<&'a str>>;
 
-fn get_successors(input: &str) -> (SuccMapVec, SuccMapSet) {
+fn get_successors(input: &str) -> (SuccMapVec<'_>, SuccMapSet<'_>) {
     let mut result: SuccMapVec = HashMap::new();
     let mut result2: SuccMapSet = HashMap::new();
     for line in input.split_ascii_whitespace() {
@@ -44,46 +44,61 @@ fn find_clique_count_n<'a>(
     succ_hash: &SuccMapSet<'a>,
     helper: &(usize, HashSet<String>),
 ) -> HashSet<String> {
+    let search = CliqueSearch {
+        check_start_t,
+        successors,
+        succ_hash,
+        cache: helper,
+    };
     let mut sorted_components_w_t: HashSet<String> = HashSet::new();
     for node in successors.keys() {
         let mut st = HashSet::new();
         st.insert(node.to_owned());
         find_clique_count(
-            check_start_t,
+            &search,
             node,
-            successors,
-            succ_hash,
             &[node],
             &mut st,
             n - 1,
             &mut sorted_components_w_t,
-            helper,
         );
     }
     sorted_components_w_t
 }
 
+/// The parts of the clique search that stay the same while expanding nodes.
+struct CliqueSearch<'s, 'a> {
+    /// are we checking for the t start (part 1)
+    check_start_t: bool,
+    /// successor maps (values are vectors/hashsets)
+    successors: &'s SuccMapVec<'a>,
+    succ_hash: &'s SuccMapSet<'a>,
+    /// cache of previous results (usize is the size of the cliques in the set)
+    cache: &'a (usize, HashSet<String>),
+}
+
 /**
- * check_start_t = are we checking for the t start (part 1)
+ * search = successor maps, cache and whether we check for the t start, see `CliqueSearch`
  * node = current node we are expanding
- * successors, succ_hash = successor map (values are vectors/hashsets)
  * nodes_sofar = list of nodes that form smaller clique
  * nodes_sofar_hash = the same as above, just a hashset (TODO)
  * n = target size
  * acc = accumulator of final cliques
- * cache = cache of previous results (usize is the size of the cliques in the set)
  * */
 fn find_clique_count<'a>(
-    check_start_t: bool,
+    search: &CliqueSearch<'_, 'a>,
     node: &'a str,
-    successors: &SuccMapVec<'a>,
-    succ_hash: &SuccMapSet<'a>,
     nodes_sofar: &[&'a str],
     nodes_sofar_hash: &mut HashSet<&'a str>,
     n: usize,
     acc: &mut HashSet<String>,
-    cache: &'a (usize, HashSet<String>),
 ) {
+    let CliqueSearch {
+        check_start_t,
+        successors,
+        succ_hash,
+        cache,
+    } = *search;
     if cache.0 == n {
         // we rely on cache on the bottom-most n layers
         let rest_of_nodes = nodes_sofar;
@@ -130,17 +145,7 @@ fn find_clique_count<'a>(
         } else {
             // n > 1 -> we need to enlarge this small-clique (new_sl)
             nodes_sofar_hash.insert(upcoming);
-            find_clique_count(
-                check_start_t,
-                upcoming,
-                successors,
-                succ_hash,
-                &new_clique,
-                nodes_sofar_hash,
-                n - 1,
-                acc,
-                cache,
-            );
+            find_clique_count(search, upcoming, &new_clique, nodes_sofar_hash, n - 1, acc);
             nodes_sofar_hash.remove(upcoming);
         }
     }
@@ -204,16 +209,19 @@ pub fn part_two(input: &str) -> Option<String> {
                 let mut st = HashSet::new();
                 st.insert(node.to_owned());
                 let mut sorted_components_w_t: HashSet<String> = HashSet::new();
+                let search = CliqueSearch {
+                    check_start_t: false,
+                    successors: &successors,
+                    succ_hash: &successors_hash,
+                    cache: &previous_cache,
+                };
                 find_clique_count(
-                    false,
+                    &search,
                     node,
-                    &successors,
-                    &successors_hash,
                     &[node],
                     &mut st,
                     clique_size - 1,
                     &mut sorted_components_w_t,
-                    &previous_cache,
                 );
                 sorted_components_w_t
             })
@@ -1,5 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
+use advent_of_code::parse::{
+    self, lines, sections, separated_pair, tag, try_map, uint, word, PResult, ParseError,
+};
+
 advent_of_code::solution!(24, 2);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -51,43 +55,33 @@ impl<'a> Calculate for Operator<'a> {
     }
 }
 
-impl<'a> From<&'a str> for Operator<'a> {
-    fn from(value: &'a str) -> Self {
-        let v = value.trim().split(" ").collect::<Vec<_>>();
-        assert!(v.len() == 3);
-        let operand_l = v[0];
-        let operand_r = v[2];
-        let operator = v[1];
-        match operator {
-            "XOR" => Self::Xor(operand_l, operand_r),
-            "OR" => Self::Or(operand_l, operand_r),
-            "AND" => Self::And(operand_l, operand_r),
-            _ => panic!("unknown operator {}", operator),
-        }
-    }
+// "x00 AND y00"
+fn operator<'a>() -> impl Fn(&'a str) -> PResult<'a, Operator<'a>> + Copy {
+    let operation = separated_pair(word(), tag(" "), separated_pair(word(), tag(" "), word()));
+    try_map(
+        operation,
+        |(operand_l, (operator, operand_r))| match operator {
+            "XOR" => Ok(Operator::Xor(operand_l, operand_r)),
+            "OR" => Ok(Operator::Or(operand_l, operand_r)),
+            "AND" => Ok(Operator::And(operand_l, operand_r)),
+            _ => Err(format!("AND, OR or XOR instead of `{operator}`")),
+        },
+    )
 }
 
-fn parse(input: &str) -> (HashMap<String, u8>, Vec<(Operator, &str)>) {
-    let mut initial_value_map = HashMap::new();
-    let lines = input.lines().collect::<Vec<_>>();
-    for l in lines.iter().take_while(|v| !v.is_empty()) {
-        let split = l.split(": ").collect::<Vec<_>>();
-        assert!(split.len() == 2);
-        initial_value_map.insert(split[0].to_owned(), split[1].parse::<u8>().unwrap());
-    }
+type Formula<'a> = (Operator<'a>, &'a str);
 
-    let mut operators = vec![];
-    for l in lines
-        .iter()
-        .skip_while(|v| !v.is_empty())
-        .skip_while(|v| v.is_empty())
-    {
-        let split = l.split(" -> ").collect::<Vec<_>>();
-        assert!(split.len() == 2);
-        operators.push((Operator::from(split[0]), split[1]));
-    }
+fn parse(input: &str) -> Result<(HashMap<String, u8>, Vec<Formula<'_>>), ParseError> {
+    let initial_value = separated_pair(word(), tag(": "), uint::<u8>());
+    let formula = separated_pair(operator(), tag(" -> "), word());
+    let (initial_values, operators) =
+        parse::parse(input, sections(lines(initial_value), lines(formula)))?;
 
-    (initial_value_map, operators)
+    let initial_value_map = initial_values
+        .into_iter()
+        .map(|(name, value)| (name.to_owned(), value))
+        .collect();
+    Ok((initial_value_map, operators))
 }
 
 fn calculate<'a, 'b>(
@@ -123,7 +117,7 @@ fn calculate<'a, 'b>(
 }
 
 pub fn part_one<'a>(input: &'a str) -> Option<u64> {
-    let (mut values, formulae) = parse(input);
+    let (mut values, formulae) = parse(input).unwrap();
     let formula_map: HashMap<&'a str, Operator<'a>> =
         HashMap::from_iter(formulae.iter().map(|(form, res_name)| (*res_name, *form)));
 
@@ -192,7 +186,7 @@ pub fn part_two<'a>(input: &'a str) -> Option<u32> {
             This layout and names should help in analysis using the algorithm below:
 
          */
-    let (values, formulae) = parse(input);
+    let (values, formulae) = parse(input).unwrap();
     // generate needed AND and XOR gates:
     let mut needed = values
         .iter()
@@ -0,0 +1,264 @@
+//! Compass directions for walking over a [`Grid`](crate::grid::Grid).
+//!
+//! Offsets are `(d_row, d_col)` with rows growing downwards, so `Up` is `(-1, 0)`.
+use std::{fmt::Display, str::FromStr};
+
+/// One of the 4 orthogonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// All directions, clockwise starting upwards.
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    /// Rotate by 90° clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        match self {
+            Dir4::Up => Dir4::Right,
+            Dir4::Right => Dir4::Down,
+            Dir4::Down => Dir4::Left,
+            Dir4::Left => Dir4::Up,
+        }
+    }
+
+    /// Rotate by 90° counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.reverse().turn_right()
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Dir4::Up => (-1, 0),
+            Dir4::Right => (0, 1),
+            Dir4::Down => (1, 0),
+            Dir4::Left => (0, -1),
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Dir4::Up | Dir4::Down)
+    }
+
+    /// The arrow character (`^>v<`) for this direction.
+    pub fn arrow(self) -> char {
+        match self {
+            Dir4::Up => '^',
+            Dir4::Right => '>',
+            Dir4::Down => 'v',
+            Dir4::Left => '<',
+        }
+    }
+}
+
+/// Parses arrows (`^v<>`), compass points (`NSEW`) and letters (`UDLR`).
+impl TryFrom<char> for Dir4 {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'N' | 'U' => Ok(Dir4::Up),
+            '>' | 'E' | 'R' => Ok(Dir4::Right),
+            'v' | 'S' | 'D' => Ok(Dir4::Down),
+            '<' | 'W' | 'L' => Ok(Dir4::Left),
+            _ => Err(format!("`{value}` is not a direction.")),
+        }
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.try_into(),
+            _ => Err(format!("`{s}` is not a direction.")),
+        }
+    }
+}
+
+impl Display for Dir4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.arrow())
+    }
+}
+
+impl From<Dir4> for (isize, isize) {
+    fn from(dir: Dir4) -> Self {
+        dir.offset()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the 8 orthogonal or diagonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    /// All directions, clockwise starting upwards.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    fn rotate(self, steps: usize) -> Self {
+        Self::ALL[(self as usize + steps) % Self::ALL.len()]
+    }
+
+    /// Rotate by 45° clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Rotate by 45° counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.rotate(7)
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Dir8::Up => (-1, 0),
+            Dir8::UpRight => (-1, 1),
+            Dir8::Right => (0, 1),
+            Dir8::DownRight => (1, 1),
+            Dir8::Down => (1, 0),
+            Dir8::DownLeft => (1, -1),
+            Dir8::Left => (0, -1),
+            Dir8::UpLeft => (-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Dir8::Up,
+            Dir4::Right => Dir8::Right,
+            Dir4::Down => Dir8::Down,
+            Dir4::Left => Dir8::Left,
+        }
+    }
+}
+
+/// Parses the single characters accepted by [`Dir4`] as well as two-letter compass points (`NE`, `SW`, ...).
+impl FromStr for Dir8 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Dir8::UpRight),
+            "SE" => Ok(Dir8::DownRight),
+            "SW" => Ok(Dir8::DownLeft),
+            "NW" => Ok(Dir8::UpLeft),
+            _ => s.parse::<Dir4>().map(Dir8::from),
+        }
+    }
+}
+
+impl From<Dir8> for (isize, isize) {
+    fn from(dir: Dir8) -> Self {
+        dir.offset()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir4, Dir8};
+
+    #[test]
+    fn rotates_dir4() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.reverse().reverse(), dir);
+            assert_ne!(dir.reverse(), dir);
+
+            let (dr, dc) = dir.offset();
+            assert_eq!(dir.reverse().offset(), (-dr, -dc));
+            // turning right maps (dr, dc) to (dc, -dr) with rows growing downwards
+            assert_eq!(dir.turn_right().offset(), (dc, -dr));
+        }
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+    }
+
+    #[test]
+    fn rotates_dir8() {
+        for dir in Dir8::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(
+                dir.turn_right().turn_right().turn_right().turn_right(),
+                dir.reverse()
+            );
+
+            let (dr, dc) = dir.offset();
+            assert_eq!(dir.reverse().offset(), (-dr, -dc));
+            assert_eq!(dir.is_diagonal(), dr != 0 && dc != 0);
+        }
+        assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+        for dir in Dir4::ALL {
+            assert_eq!(Dir8::from(dir).offset(), dir.offset());
+        }
+    }
+
+    #[test]
+    fn parses_directions() {
+        for (chars, dir) in [
+            ("^NU", Dir4::Up),
+            (">ER", Dir4::Right),
+            ("vSD", Dir4::Down),
+            ("<WL", Dir4::Left),
+        ] {
+            for c in chars.chars() {
+                assert_eq!(Dir4::try_from(c), Ok(dir));
+            }
+        }
+        assert!(Dir4::try_from('x').is_err());
+        assert!("^^".parse::<Dir4>().is_err());
+        assert_eq!(Dir4::Down.to_string(), "v");
+
+        assert_eq!("NW".parse::<Dir8>(), Ok(Dir8::UpLeft));
+        assert_eq!("<".parse::<Dir8>(), Ok(Dir8::Left));
+        assert!("NN".parse::<Dir8>().is_err());
+    }
+}
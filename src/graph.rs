@@ -0,0 +1,413 @@
+//! Shortest path searches over implicit and explicit graphs.
+//!
+//! Every search takes a `successors` function that yields the neighbours of a node. For implicit
+//! grids this is usually an iterator chain over [`Grid::neighbours_4`](crate::grid::Grid::neighbours_4),
+//! for explicit adjacency maps use [`adjacent`]. Neither allocates while expanding a node.
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use pathfinding::num_traits::Zero;
+
+/// Successors of `node` in an adjacency map, or none if the node has no entry.
+pub fn adjacent<'a, N, C>(
+    map: &'a HashMap<N, Vec<(N, C)>>,
+    node: &N,
+) -> impl Iterator<Item = (N, C)> + 'a
+where
+    N: Eq + Hash + Clone,
+    C: Copy,
+{
+    map.get(node).into_iter().flatten().cloned()
+}
+
+/// Breadth-first search for the shortest path, counted in steps, from `start` to a node that satisfies `success`.
+/// The returned path includes both `start` and the target.
+pub fn bfs<N, FN, IN, FS>(start: &N, mut successors: FN, mut success: FS) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut nodes = Nodes::new(start);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(index) = queue.pop_front() {
+        if success(&nodes.visits[index].node) {
+            return Some(nodes.path(index));
+        }
+        for next in successors(&nodes.visits[index].node) {
+            if let Entry::Vacant(entry) = nodes.index.entry(next) {
+                let next_index = nodes.visits.len();
+                nodes.visits.push(Visit {
+                    node: entry.key().clone(),
+                    parent: index,
+                    cost: (),
+                });
+                entry.insert(next_index);
+                queue.push_back(next_index);
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra search for the cheapest path from `start` to a node that satisfies `success`.
+/// The returned path includes both `start` and the target.
+pub fn dijkstra<N, C, FN, IN, FS>(start: &N, successors: FN, success: FS) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    astar(start, successors, |_| C::zero(), success)
+}
+
+/// A* search for the cheapest path from `start` to a node that satisfies `success`.
+/// `heuristic` must never overestimate the remaining cost, e.g. the manhattan distance on a grid.
+pub fn astar<N, C, FN, IN, FH, FS>(
+    start: &N,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let mut nodes = Nodes::new_with_cost(start, C::zero());
+    let mut heap = BinaryHeap::from([Candidate {
+        estimate: heuristic(start),
+        cost: C::zero(),
+        index: 0,
+    }]);
+
+    while let Some(Candidate { cost, index, .. }) = heap.pop() {
+        if cost > nodes.visits[index].cost {
+            continue;
+        }
+        if success(&nodes.visits[index].node) {
+            return Some((nodes.path(index), cost));
+        }
+        for (next, step) in successors(&nodes.visits[index].node) {
+            let next_cost = cost + step;
+            let next_index = match nodes.index.entry(next) {
+                Entry::Vacant(entry) => {
+                    let next_index = nodes.visits.len();
+                    nodes.visits.push(Visit {
+                        node: entry.key().clone(),
+                        parent: index,
+                        cost: next_cost,
+                    });
+                    entry.insert(next_index);
+                    next_index
+                }
+                Entry::Occupied(entry) => {
+                    let visit = &mut nodes.visits[*entry.get()];
+                    if next_cost >= visit.cost {
+                        continue;
+                    }
+                    visit.cost = next_cost;
+                    visit.parent = index;
+                    *entry.get()
+                }
+            };
+            heap.push(Candidate {
+                estimate: next_cost + heuristic(&nodes.visits[next_index].node),
+                cost: next_cost,
+                index: next_index,
+            });
+        }
+    }
+    None
+}
+
+/// Dijkstra search from `start` to every reachable node. Returns the cost of the cheapest path to each node.
+pub fn dijkstra_all<N, C, FN, IN>(start: &N, successors: FN) -> HashMap<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    let nodes = shortest_path_tree(start, successors, |_| false).0;
+    nodes
+        .visits
+        .into_iter()
+        .map(|visit| (visit.node, visit.cost))
+        .collect()
+}
+
+/// All nodes that lie on any of the cheapest paths from `start` to a node that satisfies `success`,
+/// together with the cost of these paths.
+pub fn shortest_path_nodes<N, C, FN, IN, FS>(
+    start: &N,
+    successors: FN,
+    success: FS,
+) -> Option<(HashSet<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    let (nodes, targets) = shortest_path_tree(start, successors, success);
+    let cost = nodes.visits[*targets.first()?].cost;
+
+    let mut on_path = vec![false; nodes.visits.len()];
+    let mut stack = targets;
+    while let Some(index) = stack.pop() {
+        if !on_path[index] {
+            on_path[index] = true;
+            stack.extend(&nodes.visits[index].parent);
+        }
+    }
+
+    let path_nodes = nodes
+        .visits
+        .into_iter()
+        .zip(on_path)
+        .filter(|(_, on_path)| *on_path)
+        .map(|(visit, _)| visit.node)
+        .collect();
+    Some((path_nodes, cost))
+}
+
+// Dijkstra that remembers every predecessor on a cheapest path. Stops once all targets with the
+// lowest cost have been found and returns their indices.
+fn shortest_path_tree<N, C, FN, IN, FS>(
+    start: &N,
+    mut successors: FN,
+    mut success: FS,
+) -> (Nodes<N, Vec<usize>, C>, Vec<usize>)
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut nodes = Nodes {
+        index: HashMap::from([(start.clone(), 0)]),
+        visits: vec![Visit {
+            node: start.clone(),
+            parent: vec![],
+            cost: C::zero(),
+        }],
+    };
+    let mut heap = BinaryHeap::from([Candidate {
+        estimate: C::zero(),
+        cost: C::zero(),
+        index: 0,
+    }]);
+    let mut targets: Vec<usize> = vec![];
+
+    while let Some(Candidate { cost, index, .. }) = heap.pop() {
+        if cost > nodes.visits[index].cost {
+            continue;
+        }
+        if let Some(first) = targets.first() {
+            if cost > nodes.visits[*first].cost {
+                break;
+            }
+        }
+        if success(&nodes.visits[index].node) {
+            targets.push(index);
+            continue;
+        }
+        for (next, step) in successors(&nodes.visits[index].node) {
+            let next_cost = cost + step;
+            let next_index = match nodes.index.entry(next) {
+                Entry::Vacant(entry) => {
+                    let next_index = nodes.visits.len();
+                    nodes.visits.push(Visit {
+                        node: entry.key().clone(),
+                        parent: vec![index],
+                        cost: next_cost,
+                    });
+                    entry.insert(next_index);
+                    next_index
+                }
+                Entry::Occupied(entry) => {
+                    let visit = &mut nodes.visits[*entry.get()];
+                    match next_cost.cmp(&visit.cost) {
+                        Ordering::Greater => continue,
+                        Ordering::Equal => {
+                            visit.parent.push(index);
+                            continue;
+                        }
+                        Ordering::Less => {
+                            visit.cost = next_cost;
+                            visit.parent = vec![index];
+                            *entry.get()
+                        }
+                    }
+                }
+            };
+            heap.push(Candidate {
+                estimate: next_cost,
+                cost: next_cost,
+                index: next_index,
+            });
+        }
+    }
+    (nodes, targets)
+}
+
+/* -------------------------------------------------------------------------- */
+
+// Visited nodes are stored once and referenced by index, so that parents and the heap don't need
+// to clone them.
+struct Nodes<N, P, C> {
+    index: HashMap<N, usize>,
+    visits: Vec<Visit<N, P, C>>,
+}
+
+struct Visit<N, P, C> {
+    node: N,
+    parent: P,
+    cost: C,
+}
+
+impl<N: Eq + Hash + Clone> Nodes<N, usize, ()> {
+    fn new(start: &N) -> Self {
+        Self::new_with_cost(start, ())
+    }
+}
+
+impl<N: Eq + Hash + Clone, C> Nodes<N, usize, C> {
+    fn new_with_cost(start: &N, cost: C) -> Self {
+        Self {
+            index: HashMap::from([(start.clone(), 0)]),
+            visits: vec![Visit {
+                node: start.clone(),
+                parent: usize::MAX,
+                cost,
+            }],
+        }
+    }
+
+    fn path(&self, mut index: usize) -> Vec<N> {
+        let mut path = vec![];
+        while index != usize::MAX {
+            path.push(self.visits[index].node.clone());
+            index = self.visits[index].parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+// Min-heap entry ordered by the estimated total cost. Ties prefer the candidate that got further.
+#[derive(PartialEq, Eq)]
+struct Candidate<C> {
+    estimate: C,
+    cost: C,
+    index: usize,
+}
+
+impl<C: Ord> Ord for Candidate<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<C: Ord> PartialOrd for Candidate<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use super::{adjacent, astar, bfs, dijkstra, dijkstra_all, shortest_path_nodes};
+    use crate::grid::Grid;
+
+    // two routes of cost 4 from S to E around the wall, and a dead end to the right
+    const MAZE: &str = "S..\n.#.\n..E\n";
+
+    fn maze() -> Grid<char> {
+        MAZE.parse().unwrap()
+    }
+
+    fn open_neighbours(
+        grid: &Grid<char>,
+        pos: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        grid.neighbours_4(pos).filter(|next| grid[*next] != '#')
+    }
+
+    #[test]
+    fn searches_grids() {
+        let grid = maze();
+        let path = bfs(&(0, 0), |p| open_neighbours(&grid, *p), |p| *p == (2, 2)).unwrap();
+        assert_eq!(path.len(), 5);
+        assert_eq!((path[0], path[4]), ((0, 0), (2, 2)));
+        assert_eq!(
+            bfs(&(0, 0), |p| open_neighbours(&grid, *p), |p| *p == (1, 1)),
+            None
+        );
+
+        let weighted = |p: &(usize, usize)| open_neighbours(&grid, *p).map(|next| (next, 2u32));
+        let (path, cost) = dijkstra(&(0, 0), weighted, |p| *p == (2, 2)).unwrap();
+        assert_eq!((path.len(), cost), (5, 8));
+
+        let manhattan = |p: &(usize, usize)| (4 - p.0 - p.1) as u32 * 2;
+        let (path, cost) = astar(&(0, 0), weighted, manhattan, |p| *p == (2, 2)).unwrap();
+        assert_eq!((path.len(), cost), (5, 8));
+    }
+
+    #[test]
+    fn searches_adjacency_maps() {
+        let map = HashMap::from([
+            ('a', vec![('b', 1), ('c', 5)]),
+            ('b', vec![('c', 1), ('d', 7)]),
+            ('c', vec![('d', 2)]),
+        ]);
+        let (path, cost) = dijkstra(&'a', |n| adjacent(&map, n), |n| *n == 'd').unwrap();
+        assert_eq!((path, cost), (vec!['a', 'b', 'c', 'd'], 4));
+
+        let costs = dijkstra_all(&'a', |n| adjacent(&map, n));
+        assert_eq!(
+            costs,
+            HashMap::from([('a', 0), ('b', 1), ('c', 2), ('d', 4)])
+        );
+    }
+
+    #[test]
+    fn finds_all_nodes_on_shortest_paths() {
+        let grid = maze();
+        let successors = |p: &(usize, usize)| open_neighbours(&grid, *p).map(|next| (next, 1));
+        let (nodes, cost) = shortest_path_nodes(&(0, 0), successors, |p| *p == (2, 2)).unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(nodes.len(), 8);
+        assert!(!nodes.contains(&(1, 1)));
+
+        // only the cheaper of two targets counts
+        let map = HashMap::from([
+            ('a', vec![('b', 1), ('c', 1)]),
+            ('b', vec![('x', 1)]),
+            ('c', vec![('y', 2)]),
+        ]);
+        let (nodes, cost) =
+            shortest_path_nodes(&'a', |n| adjacent(&map, n), |n| *n == 'x' || *n == 'y').unwrap();
+        assert_eq!((nodes, cost), (HashSet::from(['a', 'b', 'x']), 2));
+    }
+}
@@ -0,0 +1,454 @@
+//! A dense two-dimensional grid, stored row by row in a single `Vec`.
+//!
+//! Positions are `(row, col)` tuples or [`Pos`]. Methods that step away from a position take signed
+//! `(d_row, d_col)` offsets or a [`Dir4`](crate::direction::Dir4)/[`Dir8`](crate::direction::Dir8)
+//! and return `None` instead of wrapping around at the edges.
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// Offsets of the 4 orthogonal neighbours, clockwise starting upwards.
+pub const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets of all 8 neighbours, clockwise starting upwards.
+pub const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// A `(row, col)` position on a grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Step by a signed offset or direction. Returns `None` if the target lies outside of
+    /// `(height, width)`, see [`Grid::bounds`].
+    pub fn checked_add(
+        self,
+        delta: impl Into<(isize, isize)>,
+        (height, width): (usize, usize),
+    ) -> Option<Self> {
+        let (d_row, d_col) = delta.into();
+        let row = self
+            .row
+            .checked_add_signed(d_row)
+            .filter(|row| *row < height)?;
+        let col = self
+            .col
+            .checked_add_signed(d_col)
+            .filter(|col| *col < width)?;
+        Some(Self { row, col })
+    }
+
+    pub fn manhattan(self, other: Pos) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<Pos> for (usize, usize) {
+    fn from(pos: Pos) -> Self {
+        (pos.row, pos.col)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Create a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Swap rows and columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        let cells = self.columns().flat_map(|col| col.cloned()).collect();
+        Self {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from cells in row-major order.
+    ///
+    /// # Panics
+    /// If the number of cells is not a multiple of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(width * height, cells.len(), "grid is not rectangular");
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parse a grid line by line, mapping every character with its `(row, col)` position. Empty lines are skipped.
+    ///
+    /// # Panics
+    /// If the lines differ in length.
+    pub fn parse(input: &str, mapper: impl FnMut(char, (usize, usize)) -> T) -> Self {
+        Self::try_parse(input, mapper).unwrap()
+    }
+
+    fn try_parse(
+        input: &str,
+        mut mapper: impl FnMut(char, (usize, usize)) -> T,
+    ) -> Result<Self, String> {
+        let mut width = None;
+        let mut cells = vec![];
+
+        for (row, line) in input.lines().filter(|l| !l.is_empty()).enumerate() {
+            let len_before = cells.len();
+            cells.extend(
+                line.chars()
+                    .enumerate()
+                    .map(|(col, c)| mapper(c, (row, col))),
+            );
+
+            let len = cells.len() - len_before;
+            if *width.get_or_insert(len) != len {
+                return Err(format!(
+                    "line {} has length {len}, expected {}.",
+                    row + 1,
+                    width.unwrap()
+                ));
+            }
+        }
+
+        Ok(Self::from_vec(width.unwrap_or(0), cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// `(height, width)`, as expected by [`Pos::checked_add`].
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// Whether a signed position lies within the grid.
+    pub fn contains(&self, (row, col): (isize, isize)) -> bool {
+        usize::try_from(row).is_ok_and(|row| row < self.height)
+            && usize::try_from(col).is_ok_and(|col| col < self.width)
+    }
+
+    /// Step from `pos` by a signed offset or direction. Returns `None` if the target lies outside the grid.
+    pub fn offset(
+        &self,
+        pos: (usize, usize),
+        delta: impl Into<(isize, isize)>,
+    ) -> Option<(usize, usize)> {
+        Pos::from(pos)
+            .checked_add(delta, self.bounds())
+            .map(Into::into)
+    }
+
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<&T> {
+        if row < self.height && col < self.width {
+            self.cells.get(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (row, col): (usize, usize)) -> Option<&mut T> {
+        if row < self.height && col < self.width {
+            self.cells.get_mut(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    /// Get the cell at a signed position, e.g. one that was computed by adding offsets.
+    pub fn get_signed(&self, (row, col): (isize, isize)) -> Option<&T> {
+        self.get((usize::try_from(row).ok()?, usize::try_from(col).ok()?))
+    }
+
+    /// Get the cell at a signed offset from `pos`.
+    pub fn get_offset(&self, pos: (usize, usize), delta: impl Into<(isize, isize)>) -> Option<&T> {
+        self.offset(pos, delta).map(|pos| &self[pos])
+    }
+
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let (a, b) = (self.index_of(a), self.index_of(b));
+        self.cells.swap(a, b);
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Positions of the orthogonal neighbours of `pos` that lie within the grid.
+    pub fn neighbours_4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS_4
+            .iter()
+            .filter_map(move |delta| self.offset(pos, *delta))
+    }
+
+    /// Positions of all neighbours of `pos`, including diagonal ones, that lie within the grid.
+    pub fn neighbours_8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS_8
+            .iter()
+            .filter_map(move |delta| self.offset(pos, *delta))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} out of bounds");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// All diagonals running down and to the right, starting at the top right corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = (self.width, self.height);
+
+        (0..(width + height).saturating_sub(1)).map(move |i| {
+            let (row, col) = if i < width {
+                (0, width - 1 - i)
+            } else {
+                (i + 1 - width, 0)
+            };
+            (0..).map_while(move |step| self.get((row + step, col + step)))
+        })
+    }
+
+    /// All diagonals running down and to the left, starting at the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let (width, height) = (self.width, self.height);
+
+        (0..(width + height).saturating_sub(1)).map(move |i| {
+            let (row, col) = if i < width {
+                (0, i)
+            } else {
+                (i + 1 - width, width - 1)
+            };
+            (0..).map_while(move |step| self.get((row + step, col.checked_sub(step)?)))
+        })
+    }
+
+    /// Position of the first cell in row-major order that satisfies `predicate`.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter().find(|(_, v)| predicate(v)).map(|(pos, _)| pos)
+    }
+
+    /// Create a grid of the same size by mapping every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn index_of(&self, (row, col): (usize, usize)) -> usize {
+        assert!(
+            row < self.height && col < self.width,
+            "position ({row}, {col}) out of bounds"
+        );
+        row * self.width + col
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Position of the first cell in row-major order that equals `value`.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)> {
+        self.position(|v| v == value)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &Self::Output {
+        &self.cells[self.index_of(pos)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut Self::Output {
+        let index = self.index_of(pos);
+        &mut self.cells[index]
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        &self[(pos.row, pos.col)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        &mut self[(pos.row, pos.col)]
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse(s, |c, _| c)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, Pos};
+    use crate::direction::Dir4;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    fn collect<'a>(it: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+        it.map(|inner| inner.collect()).collect()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+        assert!("ab\nc".parse::<Grid<char>>().is_err());
+
+        let digits = Grid::parse("12\n34", |c, _| c.to_digit(10).unwrap());
+        assert_eq!(digits[(1, 0)], 3);
+    }
+
+    #[test]
+    fn indexes_with_offsets() {
+        let grid = grid();
+        assert_eq!(grid.offset((0, 1), (1, 1)), Some((1, 2)));
+        assert_eq!(grid.offset((0, 1), (-1, 0)), None);
+        assert_eq!(grid.offset((1, 2), (0, 1)), None);
+        assert_eq!(grid.get_offset((1, 1), (-1, -1)), Some(&'a'));
+        assert_eq!(grid.get_signed((-1, 0)), None);
+        assert!(grid.contains((1, 2)));
+        assert!(!grid.contains((0, -1)));
+    }
+
+    #[test]
+    fn steps_positions() {
+        let grid = grid();
+        let pos = Pos::new(0, 1);
+        assert_eq!(
+            pos.checked_add(Dir4::Down, grid.bounds()),
+            Some(Pos::new(1, 1))
+        );
+        assert_eq!(pos.checked_add(Dir4::Up, grid.bounds()), None);
+        assert_eq!(pos.checked_add((0, 2), grid.bounds()), None);
+        assert_eq!(grid[pos], 'b');
+        assert_eq!(grid.offset((1, 0), Dir4::Right), Some((1, 1)));
+        assert_eq!(pos.manhattan(Pos::new(1, 2)), 2);
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = grid();
+        let n4: Vec<_> = grid.neighbours_4((0, 0)).collect();
+        assert_eq!(n4, vec![(0, 1), (1, 0)]);
+        let n8: Vec<_> = grid.neighbours_8((1, 1)).collect();
+        assert_eq!(n8, vec![(0, 1), (0, 2), (1, 2), (1, 0), (0, 0)]);
+    }
+
+    #[test]
+    fn iterates_lines() {
+        let grid = grid();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(collect(grid.columns()), vec!["ad", "be", "cf"]);
+        assert_eq!(collect(grid.diagonals()), vec!["c", "bf", "ae", "d"]);
+        assert_eq!(collect(grid.anti_diagonals()), vec!["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn finds_and_transforms() {
+        let mut grid = grid();
+        assert_eq!(grid.find(&'e'), Some((1, 1)));
+        assert_eq!(grid.find(&'x'), None);
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+
+        grid.swap((0, 0), (1, 2));
+        grid[(0, 1)] = 'x';
+        assert_eq!(grid.to_string(), "fxc\ndea");
+        assert_eq!(grid.map(|c| c.is_ascii_lowercase()).find(&false), None);
+    }
+}
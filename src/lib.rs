@@ -1,16 +1,128 @@
+pub mod direction;
+pub mod graph;
+pub mod grid;
+pub mod parse;
 pub mod template;
-use regex::Regex;
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{OnceLock, RwLock},
+};
+
+use regex::{Captures, Match, Regex};
 
 // Use this file to add helper functions and additional modules.
 
+/// Compile `pattern`, or return the cached regex if it was compiled before. Safe to call from multiple threads.
+///
+/// # Panics
+/// If `pattern` is not a valid regex.
+pub fn regex(pattern: &str) -> Regex {
+    static CACHE: OnceLock<RwLock<HashMap<String, Regex>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(Default::default);
+
+    if let Some(re) = cache.read().unwrap().get(pattern) {
+        return re.clone();
+    }
+    cache
+        .write()
+        .unwrap()
+        .entry(pattern.to_owned())
+        .or_insert_with(|| Regex::new(pattern).unwrap())
+        .clone()
+}
+
 pub fn matches(pattern: &str, haystack: &str) -> bool {
-    Regex::new(pattern).unwrap().is_match(haystack)
+    regex(pattern).is_match(haystack)
+}
+
+pub fn capture_groups<'h>(pattern: &str, haystack: &'h str) -> Option<Captures<'h>> {
+    regex(pattern).captures(haystack)
+}
+
+/// All non-overlapping matches of `pattern`, with their capture groups parsed into a tuple,
+/// e.g. `captures_iter_typed::<(u32, u32)>(r"mul\((\d+),(\d+)\)", input)`.
+/// Use `Option<T>` for groups that don't take part in every match.
+///
+/// # Panics
+/// If a capture group can't be parsed into its type.
+pub fn captures_iter_typed<'h, T: FromCaptures>(
+    pattern: &str,
+    haystack: &'h str,
+) -> impl Iterator<Item = T> + 'h {
+    let re = regex(pattern);
+    let mut start = 0;
+
+    std::iter::from_fn(move || {
+        if start > haystack.len() {
+            return None;
+        }
+        let captures = re.captures_at(haystack, start)?;
+        let whole = captures.get(0).unwrap();
+        // step over empty matches so that the search makes progress
+        start = if whole.is_empty() {
+            whole.end()
+                + haystack[whole.end()..]
+                    .chars()
+                    .next()
+                    .map_or(1, char::len_utf8)
+        } else {
+            whole.end()
+        };
+        Some(T::from_captures(&captures).unwrap_or_else(|err| panic!("{err}")))
+    })
+}
+
+/// Conversion of the capture groups of a match into a tuple, see [`captures_iter_typed`].
+pub trait FromCaptures: Sized {
+    fn from_captures(captures: &Captures) -> Result<Self, String>;
 }
 
-pub fn capture_groups<'h>(pattern: &str, haystack: &'h str) -> Option<regex::Captures<'h>> {
-    Regex::new(pattern).unwrap().captures(haystack)
+/// Conversion of a single capture group, which might not have taken part in the match.
+pub trait FromGroup: Sized {
+    fn from_group(group: Option<Match>) -> Result<Self, String>;
 }
 
+impl<T: FromGroup> FromGroup for Option<T> {
+    fn from_group(group: Option<Match>) -> Result<Self, String> {
+        group.map(|g| T::from_group(Some(g))).transpose()
+    }
+}
+
+macro_rules! from_group_via_from_str {
+    ($($ty:ty),*) => {
+        $(impl FromGroup for $ty {
+            fn from_group(group: Option<Match>) -> Result<Self, String> {
+                let group = group.ok_or("capture group did not match.")?;
+                <$ty as FromStr>::from_str(group.as_str()).map_err(|_| {
+                    format!("cannot parse `{}` as {}.", group.as_str(), stringify!($ty))
+                })
+            }
+        })*
+    };
+}
+
+from_group_via_from_str!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, char, bool, String
+);
+
+macro_rules! from_captures_for_tuple {
+    ($($ty:ident $idx:literal),+) => {
+        impl<$($ty: FromGroup),+> FromCaptures for ($($ty,)+) {
+            fn from_captures(captures: &Captures) -> Result<Self, String> {
+                Ok(($($ty::from_group(captures.get($idx)).map_err(|err| format!("group {}: {err}", $idx))?,)+))
+            }
+        }
+    };
+}
+
+from_captures_for_tuple!(A 1);
+from_captures_for_tuple!(A 1, B 2);
+from_captures_for_tuple!(A 1, B 2, C 3);
+from_captures_for_tuple!(A 1, B 2, C 3, D 4);
+from_captures_for_tuple!(A 1, B 2, C 3, D 4, E 5);
+from_captures_for_tuple!(A 1, B 2, C 3, D 4, E 5, F 6);
+
 pub fn parse_field<S, T>(input: &str, mut mapper: T) -> Vec<Vec<S>>
 where
     T: FnMut(char, (usize, usize)) -> S,
@@ -27,3 +139,48 @@ where
         })
         .collect()
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{capture_groups, captures_iter_typed, matches, regex};
+
+    #[test]
+    fn caches_regexes() {
+        let pattern = r"^\d+$";
+        assert!(matches(pattern, "123"));
+        assert!(!matches(pattern, "12a"));
+        assert_eq!(regex(pattern).as_str(), pattern);
+        assert_eq!(&capture_groups(r"(\w)=(\d)", "a=1").unwrap()[2], "1");
+    }
+
+    #[test]
+    fn parses_typed_captures() {
+        let muls = captures_iter_typed::<(u32, u32)>(r"mul\((\d+),(\d+)\)", "mul(2,4)%mul(5,5)");
+        assert_eq!(muls.collect::<Vec<_>>(), vec![(2, 4), (5, 5)]);
+
+        let pattern = r"mul\((\d+),(\d+)\)|(do(?:n't)?)\(\)";
+        let found = captures_iter_typed::<(Option<u32>, Option<i64>, Option<String>)>(
+            pattern,
+            "mul(2,4)don't()_mul(5,5)do()",
+        )
+        .collect::<Vec<_>>();
+        assert_eq!(found[0], (Some(2), Some(4), None));
+        assert_eq!(found[1], (None, None, Some("don't".into())));
+        assert_eq!(found.len(), 4);
+
+        // empty matches advance by one character
+        let empty = captures_iter_typed::<(Option<char>,)>("(x)?", "ax");
+        assert_eq!(
+            empty.collect::<Vec<_>>(),
+            vec![(None,), (Some('x'),), (None,)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "group 1: cannot parse `300` as u8.")]
+    fn reports_typed_capture_errors() {
+        let _ = captures_iter_typed::<(u8,)>(r"(\d+)", "300").count();
+    }
+}
@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, example, read, scaffold, solve, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,34 +9,48 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        commands::time,
+        input::{Input, STDIN_PATH},
+        PuzzleId,
+    };
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Example {
+            puzzle: PuzzleId,
+            block: Option<usize>,
+            overwrite: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: Input,
+            params: Vec<String>,
         },
         All {
             release: bool,
+            in_process: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
         },
-        Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
+        Time(time::Options),
+        Verify {
+            accept: bool,
         },
         #[cfg(feature = "today")]
         Today,
@@ -44,35 +60,121 @@ mod args {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+            Some("all") => {
+                let in_process = args.contains("--in-process");
+                let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+                let timeout = parse_timeout(&mut args, in_process)?;
+
+                if in_process && jobs > 1 {
+                    eprintln!("`--in-process` and `--jobs` cannot be combined.");
+                    process::exit(1);
+                }
+
+                AppArguments::All {
+                    release: args.contains("--release"),
+                    in_process,
+                    jobs,
+                    timeout,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let threshold = args.opt_value_from_str("--threshold")?;
+                let in_process = args.contains("--in-process");
+                let timeout = parse_timeout(&mut args, in_process)?;
+                let memory = args.contains("--memory");
+                let history = args.opt_value_from_str("--history")?;
+                let formats = args.values_from_str("--format")?;
 
-                AppArguments::Time {
-                    all,
+                if store && compare {
+                    eprintln!("`--store` and `--compare` cannot be combined.");
+                    process::exit(1);
+                }
+
+                if !formats.is_empty() && !store {
+                    eprintln!("`--format` can only be used together with `--store`.");
+                    process::exit(1);
+                }
+
+                if in_process && memory {
+                    // the `aoc_all` binary cannot be built with dhat, see `build.rs`.
+                    eprintln!("`--in-process` and `--memory` cannot be combined.");
+                    process::exit(1);
+                }
+
+                if history.is_some() && (store || compare) {
+                    eprintln!("`--history` cannot be combined with `--store` or `--compare`.");
+                    process::exit(1);
+                }
+
+                AppArguments::Time(time::Options {
                     day: args.opt_free_from_str()?,
+                    all,
                     store,
-                }
+                    formats,
+                    compare,
+                    threshold,
+                    in_process,
+                    timeout,
+                    memory,
+                    history,
+                })
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+                puzzle: args.free_from_str()?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("example") => {
+                let block = args.opt_value_from_str("--block")?;
+                let overwrite = args.contains("--overwrite");
+
+                AppArguments::Example {
+                    puzzle: args.free_from_str()?,
+                    block,
+                    overwrite,
+                }
+            }
+            Some("scaffold") => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+                let template = args.opt_value_from_str("--template")?;
+
+                AppArguments::Scaffold {
+                    puzzle: args.free_from_str()?,
+                    download,
+                    overwrite,
+                    template,
+                }
+            }
+            Some("solve") => {
+                let puzzle = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let params = args.values_from_str("--param")?;
+                // NOTE: parsed last, since the optional example number is a free argument.
+                let input = parse_input(&mut args)?;
+
+                if submit.is_some() && input != Input::Puzzle {
+                    eprintln!("Only results for the puzzle input can be submitted.");
+                    process::exit(1);
+                }
+
+                AppArguments::Solve {
+                    puzzle,
+                    release,
+                    submit,
+                    dhat,
+                    input,
+                    params,
+                }
+            }
+            Some("verify") => AppArguments::Verify {
+                accept: args.contains("--accept"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -93,6 +195,46 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parse `--input <path>` and `--example [N]`, which select the input of `solve`.
+    fn parse_input(args: &mut pico_args::Arguments) -> Result<Input, pico_args::Error> {
+        let path: Option<String> = args.opt_value_from_str("--input")?;
+        let example = if args.contains("--example") {
+            Some(args.opt_free_from_str::<u8>()?)
+        } else {
+            None
+        };
+
+        let input = match (path, example) {
+            (Some(_), Some(_)) => {
+                eprintln!("`--input` and `--example` cannot be combined.");
+                process::exit(1);
+            }
+            (Some(path), None) if path == STDIN_PATH => Input::Stdin,
+            (Some(path), None) => Input::File(path.into()),
+            (None, Some(n)) => Input::Example(n),
+            (None, None) => Input::Puzzle,
+        };
+
+        Ok(input)
+    }
+
+    /// Parse `--timeout <seconds>`, which requires every day to run in its own process.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+        in_process: bool,
+    ) -> Result<Option<Duration>, pico_args::Error> {
+        let timeout = args
+            .opt_value_from_str("--timeout")?
+            .map(Duration::from_secs);
+
+        if in_process && timeout.is_some() {
+            eprintln!("`--in-process` and `--timeout` cannot be combined.");
+            process::exit(1);
+        }
+
+        Ok(timeout)
+    }
 }
 
 fn main() {
@@ -102,33 +244,48 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                release,
+                in_process,
+                jobs,
+                timeout,
+            } => all::handle(release, in_process, jobs, timeout),
+            AppArguments::Time(options) => time::handle(&options),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Example {
+                puzzle,
+                block,
+                overwrite,
+            } => example::handle(puzzle, block, overwrite),
             AppArguments::Scaffold {
-                day,
+                puzzle,
                 download,
                 overwrite,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                // download first, so that the template can use the title of the puzzle.
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
+                scaffold::handle(puzzle, overwrite, template.as_deref());
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+                params,
+            } => solve::handle(puzzle, release, dhat, submit, &input, &params),
+            AppArguments::Verify { accept } => verify::handle(accept),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        download::handle(day.into());
+                        scaffold::handle(day.into(), false, None);
+                        read::handle(day.into())
                     }
                     None => {
                         eprintln!(
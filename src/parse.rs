@@ -0,0 +1,407 @@
+//! Small parser combinators for puzzle inputs.
+//!
+//! A parser is any `Fn(&str) -> PResult<T>` that consumes a prefix of its input and returns the
+//! parsed value together with the rest. Combine them with the functions in this module and run the
+//! result with [`parse`], which reports failures with their line and column:
+//!
+//! ```
+//! use advent_of_code::parse::{self, field, int, lines, separated_pair, tag};
+//!
+//! let coords = || separated_pair(int::<i64>(), tag(","), int());
+//! let robot = separated_pair(field("p=", coords()), tag(" "), field("v=", coords()));
+//! let robots = parse::parse("p=0,4 v=3,-3\np=6,3 v=-1,-3\n", lines(robot)).unwrap();
+//! assert_eq!(robots[1], ((6, 3), (-1, -3)));
+//!
+//! let err = parse::parse("p=0,4 v=3,-3\np=6,x v=-1,-3\n", lines(robot)).unwrap_err();
+//! assert_eq!(err.to_string(), "line 2, column 5: expected integer, found `x v=-1,-3`");
+//! ```
+use std::{fmt, str::FromStr};
+
+use crate::grid::Grid;
+
+/// Result of a parser: the value and the remaining input, or where and why parsing failed.
+pub type PResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// A parse failure at a position within the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<'a> {
+    at: &'a str,
+    expected: String,
+}
+
+impl<'a> Failure<'a> {
+    pub fn new(at: &'a str, expected: impl Into<String>) -> Self {
+        Self {
+            at,
+            expected: expected.into(),
+        }
+    }
+}
+
+/// A parse failure with its position resolved to a 1-based line and column.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    fn from_failure(input: &str, failure: Failure) -> Self {
+        // failures always point into `input`, since parsers only ever hand out sub-slices of it.
+        let offset = (failure.at.as_ptr() as usize)
+            .saturating_sub(input.as_ptr() as usize)
+            .min(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        let found = failure.at.lines().next().unwrap_or_default();
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: failure.expected,
+            found: if found.is_empty() {
+                "end of line".into()
+            } else {
+                format!("`{found}`")
+            },
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+// `unwrap()` on a parse result should print the readable message.
+impl fmt::Debug for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Run `parser` on the whole `input`. Trailing whitespace is ignored.
+pub fn parse<'a, T>(
+    input: &'a str,
+    parser: impl Fn(&'a str) -> PResult<'a, T>,
+) -> Result<T, ParseError> {
+    complete(&parser, input, "end of input").map_err(|f| ParseError::from_failure(input, f))
+}
+
+fn complete<'a, T>(
+    parser: &impl Fn(&'a str) -> PResult<'a, T>,
+    input: &'a str,
+    expected: &str,
+) -> Result<T, Failure<'a>> {
+    let (value, rest) = parser(input)?;
+    if rest.trim_end().is_empty() {
+        Ok(value)
+    } else {
+        Err(Failure::new(rest, expected))
+    }
+}
+
+fn number<'a, T: FromStr>(input: &'a str, len: usize, expected: &str) -> PResult<'a, T> {
+    match input[..len].parse() {
+        Ok(value) if len > 0 => Ok((value, &input[len..])),
+        _ => Err(Failure::new(input, expected)),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An unsigned integer, e.g. `42`.
+pub fn uint<'a, T: FromStr>() -> impl Fn(&'a str) -> PResult<'a, T> + Copy {
+    |input| {
+        let len = input.bytes().take_while(u8::is_ascii_digit).count();
+        number(input, len, "unsigned integer")
+    }
+}
+
+/// An integer with an optional sign, e.g. `-42` or `+42`.
+pub fn int<'a, T: FromStr>() -> impl Fn(&'a str) -> PResult<'a, T> + Copy {
+    |input| {
+        let sign = usize::from(input.starts_with(['-', '+']));
+        let digits = input[sign..].bytes().take_while(u8::is_ascii_digit).count();
+        let len = if digits == 0 { 0 } else { sign + digits };
+        number(input, len, "integer")
+    }
+}
+
+/// Exactly the string `expected`.
+pub fn tag<'a>(expected: &'static str) -> impl Fn(&'a str) -> PResult<'a, &'a str> + Copy {
+    move |input| match input.strip_prefix(expected) {
+        Some(rest) => Ok((&input[..expected.len()], rest)),
+        None => Err(Failure::new(input, format!("`{expected}`"))),
+    }
+}
+
+/// A non-empty run of alphanumeric characters or `_`.
+pub fn word<'a>() -> impl Fn(&'a str) -> PResult<'a, &'a str> + Copy {
+    |input| {
+        let len = input
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(input.len());
+        if len == 0 {
+            Err(Failure::new(input, "word"))
+        } else {
+            Ok((&input[..len], &input[len..]))
+        }
+    }
+}
+
+/// A value preceded by a label, e.g. `field("Register A: ", uint())` for `Register A: 729`.
+pub fn field<'a, T>(
+    label: &'static str,
+    parser: impl Fn(&'a str) -> PResult<'a, T> + Copy,
+) -> impl Fn(&'a str) -> PResult<'a, T> + Copy {
+    preceded(tag(label), parser)
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn map<'a, A, B>(
+    parser: impl Fn(&'a str) -> PResult<'a, A> + Copy,
+    f: impl Fn(A) -> B + Copy,
+) -> impl Fn(&'a str) -> PResult<'a, B> + Copy {
+    move |input| parser(input).map(|(value, rest)| (f(value), rest))
+}
+
+/// Map the parsed value with a fallible function. Errors are reported at the start of the value.
+pub fn try_map<'a, A, B>(
+    parser: impl Fn(&'a str) -> PResult<'a, A> + Copy,
+    f: impl Fn(A) -> Result<B, String> + Copy,
+) -> impl Fn(&'a str) -> PResult<'a, B> + Copy {
+    move |input| {
+        let (value, rest) = parser(input)?;
+        match f(value) {
+            Ok(value) => Ok((value, rest)),
+            Err(expected) => Err(Failure::new(input, expected)),
+        }
+    }
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Fn(&'a str) -> PResult<'a, A> + Copy,
+    second: impl Fn(&'a str) -> PResult<'a, B> + Copy,
+) -> impl Fn(&'a str) -> PResult<'a, (A, B)> + Copy {
+    move |input| {
+        let (a, rest) = first(input)?;
+        let (b, rest) = second(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+pub fn preceded<'a, A, B>(
+    first: impl Fn(&'a str) -> PResult<'a, A> + Copy,
+    second: impl Fn(&'a str) -> PResult<'a, B> + Copy,
+) -> impl Fn(&'a str) -> PResult<'a, B> + Copy {
+    map(pair(first, second), |(_, b)| b)
+}
+
+pub fn terminated<'a, A, B>(
+    first: impl Fn(&'a str) -> PResult<'a, A> + Copy,
+    second: impl Fn(&'a str) -> PResult<'a, B> + Copy,
+) -> impl Fn(&'a str) -> PResult<'a, A> + Copy {
+    map(pair(first, second), |(a, _)| a)
+}
+
+pub fn separated_pair<'a, A, S, B>(
+    first: impl Fn(&'a str) -> PResult<'a, A> + Copy,
+    separator: impl Fn(&'a str) -> PResult<'a, S> + Copy,
+    second: impl Fn(&'a str) -> PResult<'a, B> + Copy,
+) -> impl Fn(&'a str) -> PResult<'a, (A, B)> + Copy {
+    pair(terminated(first, separator), second)
+}
+
+/// One or more values separated by `separator`, e.g. `separated(uint(), ",")` for `1,2,3`.
+pub fn separated<'a, T>(
+    parser: impl Fn(&'a str) -> PResult<'a, T> + Copy,
+    separator: &'static str,
+) -> impl Fn(&'a str) -> PResult<'a, Vec<T>> + Copy {
+    move |input| {
+        let (first, mut rest) = parser(input)?;
+        let mut values = vec![first];
+        while let Some(next) = rest.strip_prefix(separator) {
+            let (value, next) = parser(next)?;
+            values.push(value);
+            rest = next;
+        }
+        Ok((values, rest))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Every non-empty line of the remaining input, each parsed completely by `parser`.
+pub fn lines<'a, T>(
+    parser: impl Fn(&'a str) -> PResult<'a, T> + Copy,
+) -> impl Fn(&'a str) -> PResult<'a, Vec<T>> + Copy {
+    move |input| {
+        let values = input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| complete(&parser, line, "end of line"))
+            .collect::<Result<_, _>>()?;
+        Ok((values, &input[input.len()..]))
+    }
+}
+
+/// Every block of the remaining input, separated by blank lines, each parsed completely by `parser`.
+pub fn blocks<'a, T>(
+    parser: impl Fn(&'a str) -> PResult<'a, T> + Copy,
+) -> impl Fn(&'a str) -> PResult<'a, Vec<T>> + Copy {
+    move |input| {
+        let values = input
+            .split("\n\n")
+            .map(|block| block.trim_start_matches('\n'))
+            .filter(|block| !block.trim().is_empty())
+            .map(|block| complete(&parser, block, "end of block"))
+            .collect::<Result<_, _>>()?;
+        Ok((values, &input[input.len()..]))
+    }
+}
+
+/// The remaining input split at the first blank line: `first` parses everything before it and
+/// `second` everything after it.
+pub fn sections<'a, A, B>(
+    first: impl Fn(&'a str) -> PResult<'a, A> + Copy,
+    second: impl Fn(&'a str) -> PResult<'a, B> + Copy,
+) -> impl Fn(&'a str) -> PResult<'a, (A, B)> + Copy {
+    move |input| {
+        let Some(split) = input.find("\n\n") else {
+            return Err(Failure::new(&input[input.len()..], "blank line"));
+        };
+        let a = complete(&first, &input[..split], "blank line")?;
+        let b = complete(
+            &second,
+            input[split..].trim_start_matches('\n'),
+            "end of input",
+        )?;
+        Ok(((a, b), &input[input.len()..]))
+    }
+}
+
+/// The remaining input as a rectangular grid, mapping every character with `cell`.
+pub fn grid<'a, T>(
+    cell: impl Fn(char) -> Option<T> + Copy,
+) -> impl Fn(&'a str) -> PResult<'a, Grid<T>> + Copy {
+    move |input| {
+        let mut width = None;
+        let mut cells = vec![];
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let len_before = cells.len();
+            for (idx, c) in line.char_indices() {
+                cells.push(cell(c).ok_or_else(|| Failure::new(&line[idx..], "grid cell"))?);
+            }
+
+            let len = cells.len() - len_before;
+            let expected = *width.get_or_insert(len);
+            if len != expected {
+                return Err(Failure::new(line, format!("line of length {expected}")));
+            }
+        }
+        Ok((
+            Grid::from_vec(width.unwrap_or(0), cells),
+            &input[input.len()..],
+        ))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    fn error<T: fmt::Debug>(result: Result<T, ParseError>) -> (usize, usize, String) {
+        let err = result.unwrap_err();
+        (err.line, err.column, err.expected)
+    }
+
+    #[test]
+    fn parses_numbers() {
+        assert_eq!(parse("42", uint::<u32>()), Ok(42));
+        assert_eq!(parse("-42", int::<i64>()), Ok(-42));
+        assert_eq!(parse("+7", int::<i8>()), Ok(7));
+        assert_eq!(error(parse("-", int::<i64>())), (1, 1, "integer".into()));
+        assert_eq!(
+            error(parse("-1", uint::<u32>())),
+            (1, 1, "unsigned integer".into())
+        );
+        assert_eq!(
+            error(parse("300", uint::<u8>())),
+            (1, 1, "unsigned integer".into())
+        );
+        assert_eq!(
+            error(parse("1 2", uint::<u8>())),
+            (1, 2, "end of input".into())
+        );
+    }
+
+    #[test]
+    fn parses_sequences() {
+        let list = separated(uint::<u32>(), ",");
+        assert_eq!(parse("1,2,3", list), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            error(parse("1,2,", list)),
+            (1, 5, "unsigned integer".into())
+        );
+
+        let register = field("Register A: ", uint::<u64>());
+        assert_eq!(parse("Register A: 729", register), Ok(729));
+        assert_eq!(
+            error(parse("Register B: 1", register)),
+            (1, 1, "`Register A: `".into())
+        );
+
+        let wire = separated_pair(word(), tag(": "), uint::<u8>());
+        assert_eq!(parse("x00: 1", wire), Ok(("x00", 1)));
+
+        let parity = try_map(uint::<u32>(), |v| {
+            (v % 2 == 0).then_some(v).ok_or("even number".to_string())
+        });
+        assert_eq!(error(parse("3", parity)), (1, 1, "even number".into()));
+    }
+
+    #[test]
+    fn parses_lines_and_blocks() {
+        let rules = lines(separated_pair(uint::<u32>(), tag("|"), uint()));
+        let updates = lines(separated(uint::<u32>(), ","));
+        let input = "47|53\n97|13\n\n75,47\n97,x\n";
+
+        let err = parse(input, sections(rules, updates)).unwrap_err();
+        assert_eq!((err.line, err.column), (5, 4));
+        assert_eq!(
+            err.to_string(),
+            "line 5, column 4: expected unsigned integer, found `x`"
+        );
+
+        let (rules, updates) = parse("47|53\n\n75,47\n", sections(rules, updates)).unwrap();
+        assert_eq!((rules, updates), (vec![(47, 53)], vec![vec![75, 47]]));
+
+        let sums = blocks(map(lines(uint::<u32>()), |v| v.iter().sum::<u32>()));
+        assert_eq!(parse("1\n2\n\n3\n\n\n4\n", sums), Ok(vec![3, 3, 4]));
+    }
+
+    #[test]
+    fn parses_grids() {
+        let digits = grid(|c| c.to_digit(10));
+        let parsed = parse("12\n34\n", digits).unwrap();
+        assert_eq!(parsed[(1, 0)], 3);
+        assert_eq!(error(parse("12\n3x\n", digits)), (2, 2, "grid cell".into()));
+        assert_eq!(
+            error(parse("12\n345\n", digits)),
+            (2, 1, "line of length 2".into())
+        );
+    }
+}
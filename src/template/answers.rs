@@ -0,0 +1,308 @@
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::part_report::{PartReport, PartStatus};
+use crate::template::PuzzleId;
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Represents the known-correct answers of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: PuzzleId,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the known-correct answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+/// Outcome of checking a part's answer against the stored one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    Missing {
+        actual: String,
+    },
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        let s = fs::read_to_string(ANSWERS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from);
+
+        match s {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                Answers::default()
+            }
+        }
+    }
+
+    pub fn get(&self, day: PuzzleId, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Record `value` as the correct answer, overwriting a previously stored one.
+    pub fn set(&mut self, day: PuzzleId, part: u8, value: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answer = &mut self.data[index];
+        match part {
+            1 => answer.part_1 = Some(value.into()),
+            2 => answer.part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+
+    /// Check the reports of a day's run against the stored answers.
+    /// Parts that are neither solved nor have a stored answer are skipped.
+    pub fn verify(&self, day: PuzzleId, reports: &[PartReport]) -> Vec<(u8, Verdict)> {
+        [1, 2]
+            .into_iter()
+            .filter_map(|part| {
+                let actual = reports
+                    .iter()
+                    .find(|r| r.part == part && r.status == PartStatus::Solved)
+                    .and_then(|r| r.answer.clone());
+
+                let verdict = match (self.get(day, part), actual) {
+                    (Some(expected), Some(actual)) if expected == actual => Verdict::Pass,
+                    (Some(expected), actual) => Verdict::Fail {
+                        expected: expected.into(),
+                        actual,
+                    },
+                    (None, Some(actual)) => Verdict::Missing { actual },
+                    (None, None) => return None,
+                };
+
+                Some((part, verdict))
+            })
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| PuzzleId::from_str(day).ok())
+            .ok_or("Expected answer.day to be a puzzle id.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, Answers, Verdict};
+    use crate::{
+        puzzle,
+        template::{
+            part_report::{PartReport, PartStatus},
+            timings::PartStats,
+        },
+    };
+
+    fn report(part: u8, answer: Option<&str>) -> PartReport {
+        PartReport {
+            day: puzzle!(1),
+            part,
+            status: if answer.is_some() {
+                PartStatus::Solved
+            } else {
+                PartStatus::Unsolved
+            },
+            answer: answer.map(Into::into),
+            error: None,
+            stats: PartStats::default(),
+            memory: None,
+        }
+    }
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![Answer {
+                day: puzzle!(1),
+                part_1: Some("11".into()),
+                part_2: Some("31".into()),
+            }],
+        }
+    }
+
+    #[test]
+    fn deserializes_answers() {
+        let json = r#"{ "data": [{ "day": "09", "part_1": "1928", "part_2": null }] }"#;
+        let answers = Answers::try_from(json.to_string()).unwrap();
+        assert_eq!(answers.get(puzzle!(9), 1), Some("1928"));
+        assert_eq!(answers.get(puzzle!(9), 2), None);
+        assert_eq!(answers.get(puzzle!(1), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "26", "part_1": null, "part_2": null }] }"#;
+        Answers::try_from(json.to_string()).unwrap();
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = get_mock_answers();
+        answers.set(puzzle!(3), 2, "48");
+        answers.set(puzzle!(1), 2, "32");
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[1].day, puzzle!(3));
+        assert_eq!(answers.get(puzzle!(3), 2), Some("48"));
+        assert_eq!(answers.get(puzzle!(1), 2), Some("32"));
+    }
+
+    #[test]
+    fn verifies_reports() {
+        let answers = get_mock_answers();
+        let res = answers.verify(puzzle!(1), &[report(1, Some("11")), report(2, Some("30"))]);
+        assert_eq!(res[0], (1, Verdict::Pass));
+        assert_eq!(
+            res[1],
+            (
+                2,
+                Verdict::Fail {
+                    expected: "31".into(),
+                    actual: Some("30".into())
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn verifies_unsolved_and_unknown_parts() {
+        let answers = get_mock_answers();
+        let res = answers.verify(puzzle!(1), &[report(1, None)]);
+        assert!(matches!(res[0], (1, Verdict::Fail { actual: None, .. })));
+
+        let res = answers.verify(puzzle!(2), &[report(1, Some("2")), report(2, None)]);
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0], (1, Verdict::Missing { actual: "2".into() }));
+    }
+}
@@ -0,0 +1,256 @@
+/// Convert every `<article>` of an Advent of Code page to Markdown. Returns an empty string if
+/// there is none.
+///
+/// Only the handful of elements used in puzzle descriptions are supported, all other tags are
+/// dropped and their text is kept.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let content = &rest[start..];
+        let content = &content[content.find('>').map_or(content.len(), |i| i + 1)..];
+        let end = content.find("</article>").unwrap_or(content.len());
+
+        block_break(&mut out);
+        convert(&content[..end], &mut out);
+        rest = &content[end..];
+    }
+
+    let trimmed = out.trim();
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("{trimmed}\n")
+    }
+}
+
+#[derive(Default)]
+struct State {
+    in_pre: bool,
+    in_code: bool,
+    /// Whether the current inline code is emphasized, which is how answers are highlighted.
+    in_emphasized_code: bool,
+    links: Vec<String>,
+}
+
+fn convert(html: &str, out: &mut String) {
+    let mut state = State::default();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            text(rest, &state, out);
+            break;
+        };
+
+        text(&rest[..tag_start], &state, out);
+
+        let tag_end = rest[tag_start..]
+            .find('>')
+            .map_or(rest.len(), |i| tag_start + i + 1);
+        tag(&rest[tag_start..tag_end], &mut state, out);
+        rest = &rest[tag_end..];
+    }
+}
+
+fn tag(tag: &str, state: &mut State, out: &mut String) {
+    let inner = tag.trim_start_matches('<').trim_end_matches('>');
+    let closing = inner.starts_with('/');
+    let inner = inner.trim_start_matches('/');
+    let name = inner
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    match (name.as_str(), closing) {
+        ("h2", false) => {
+            block_break(out);
+            out.push_str("## ");
+        }
+        ("p" | "h2" | "ul", _) => block_break(out),
+        ("pre", false) => {
+            block_break(out);
+            out.push_str("```\n");
+            state.in_pre = true;
+        }
+        ("pre", true) => {
+            if !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str("```");
+            block_break(out);
+            state.in_pre = false;
+        }
+        ("code", false) if !state.in_pre => {
+            out.push('`');
+            state.in_code = true;
+        }
+        ("code", true) if !state.in_pre => {
+            out.push_str(if state.in_emphasized_code { "`*" } else { "`" });
+            state.in_code = false;
+            state.in_emphasized_code = false;
+        }
+        // `<code><em>11</em></code>` becomes *`11`*, since emphasis is not rendered within code.
+        ("em", false) if state.in_code && out.ends_with('`') => {
+            out.pop();
+            out.push_str("*`");
+            state.in_emphasized_code = true;
+        }
+        ("em", _) if !state.in_pre && !state.in_code => out.push('*'),
+        ("li", false) => {
+            if !out.is_empty() && !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str("- ");
+        }
+        ("li", true) => out.push('\n'),
+        ("br", _) => out.push('\n'),
+        ("a", false) => {
+            state
+                .links
+                .push(attribute(inner, "href").unwrap_or_default());
+            out.push('[');
+        }
+        ("a", true) => {
+            let href = state.links.pop().unwrap_or_default();
+            out.push_str(&format!("]({href})"));
+        }
+        _ => {}
+    }
+}
+
+fn text(text: &str, state: &State, out: &mut String) {
+    let text = decode_entities(text);
+
+    if state.in_pre {
+        out.push_str(&text);
+    } else if !(text.trim().is_empty() && text.contains('\n')) {
+        // line breaks in the markup are not significant outside of code blocks.
+        out.push_str(&text.replace('\n', " "));
+    }
+}
+
+/// Make sure that `out` ends with a blank line, unless it is empty.
+fn block_break(out: &mut String) {
+    if out.is_empty() {
+        return;
+    }
+
+    while !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')? + start;
+    Some(decode_entities(&tag[start..end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_entities, html_to_markdown};
+
+    #[test]
+    fn converts_puzzle_descriptions() {
+        let html = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is <a href="/2024/day/2">missing</a>.</p>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+
+2   5
+</code></pre>
+<ul>
+<li>The first pair is <code>3</code>.</li>
+<li>Its distance is <code><em>1</em></code> &amp; <em><code>2</code></em>.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>11</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Again &lt;3&#39;s.</p></article>
+</main>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Historian Hysteria ---
+
+The *Chief Historian* is [missing](/2024/day/2).
+
+For example:
+
+```
+3   4
+4   3
+
+2   5
+```
+
+- The first pair is `3`.
+- Its distance is *`1`* & *`2`*.
+
+## --- Part Two ---
+
+Again <3's.
+"
+        );
+    }
+
+    #[test]
+    fn handles_pages_without_articles() {
+        assert_eq!(html_to_markdown("<html><body>Not Found</body></html>"), "");
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &amp;&amp; b &#x3E; &#62; &bogus c"),
+            "a && b > > &bogus c"
+        );
+    }
+}
@@ -0,0 +1,512 @@
+/// Module that talks to the Advent of Code website.
+///
+/// Requests are authenticated with the session cookie of the user, which is read from the
+/// `AOC_SESSION` environment variable or the file `~/.adventofcode.session`.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use ureq::Agent;
+
+use crate::template::{PuzzleId, MAIN_YEAR};
+
+mod markdown;
+
+pub use markdown::html_to_markdown;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies this repository, as requested by the Advent of Code automation guidelines.
+/// Can be overridden with `AOC_USER_AGENT`, e.g. to add contact details.
+pub const DEFAULT_USER_AGENT: &str = "github.com/BohdanQQ/aoc-2024";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    YearNotSet,
+    /// The request could not be sent or the response could not be read.
+    Request(String),
+    BadStatus(u16),
+    /// The response did not contain what we were looking for.
+    UnexpectedResponse(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create the file `~/.adventofcode.session`."
+            ),
+            AocClientError::YearNotSet => write!(f, "`AOC_YEAR` is not set. Set it or prefix the day with a year, like `2023/05`."),
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::BadStatus(404) => write!(
+                f,
+                "the puzzle was not found. Is it unlocked already?"
+            ),
+            AocClientError::BadStatus(400) => write!(
+                f,
+                "the request was rejected. Has the session cookie expired?"
+            ),
+            AocClientError::BadStatus(status) => {
+                write!(f, "server responded with status {status}.")
+            }
+            AocClientError::UnexpectedResponse(e) => write!(f, "unexpected response: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        AocClientError::Request(e.to_string())
+    }
+}
+
+/// Verdict of the website on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionResult {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, the next one is accepted after the given wait.
+    RateLimited(Duration),
+    /// The part was already solved, or part two is not unlocked yet.
+    WrongLevel,
+}
+
+impl SubmissionResult {
+    /// Parse the text of the response to a submission.
+    fn from_response(text: &str) -> Option<Self> {
+        if text.contains("That's the right answer") {
+            Some(SubmissionResult::Correct)
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Some(SubmissionResult::TooHigh)
+            } else if text.contains("your answer is too low") {
+                Some(SubmissionResult::TooLow)
+            } else {
+                Some(SubmissionResult::Incorrect)
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Some(SubmissionResult::RateLimited(parse_wait(text)?))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(SubmissionResult::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for SubmissionResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionResult::Correct => write!(f, "That's the right answer!"),
+            SubmissionResult::Incorrect => write!(f, "That's not the right answer."),
+            SubmissionResult::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            SubmissionResult::TooLow => write!(f, "That's not the right answer, it is too low."),
+            SubmissionResult::RateLimited(wait) => write!(
+                f,
+                "You gave an answer too recently, wait {}s before submitting again.",
+                wait.as_secs()
+            ),
+            SubmissionResult::WrongLevel => write!(
+                f,
+                "This part is not open for answers. Did you already complete it?"
+            ),
+        }
+    }
+}
+
+/// Parse the wait of a rate limited submission, e.g. "You have 1m 5s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+    let end = text.find(" left to wait")?;
+    let start = text[..end].rfind("You have ")? + "You have ".len();
+
+    text[start..end]
+        .split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3_600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+fn build_agent(user_agent: &str) -> Agent {
+    Agent::config_builder()
+        .http_status_as_error(false)
+        .user_agent(user_agent)
+        .timeout_global(Some(Duration::from_secs(30)))
+        .build()
+        .into()
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    /// Year of the main event, used for puzzles without a year.
+    year: Option<u16>,
+    agent: Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>, year: Option<u16>) -> Self {
+        AocClient {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            year,
+            agent: build_agent(DEFAULT_USER_AGENT),
+        }
+    }
+
+    /// Send requests with the given user agent instead of [`DEFAULT_USER_AGENT`].
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.agent = build_agent(user_agent);
+        self
+    }
+
+    /// Create a client for the main event in [`MAIN_YEAR`]. The base url can be overridden with `AOC_BASE_URL`,
+    /// the user agent with `AOC_USER_AGENT`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        let client = AocClient::new(base_url, read_session()?, MAIN_YEAR);
+
+        Ok(match env::var("AOC_USER_AGENT") {
+            Ok(user_agent) => client.with_user_agent(&user_agent),
+            Err(_) => client,
+        })
+    }
+
+    fn day_url(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let year = puzzle
+            .year
+            .or(self.year)
+            .ok_or(AocClientError::YearNotSet)?;
+        Ok(format!(
+            "{}/{year}/day/{}",
+            self.base_url,
+            puzzle.day.into_inner()
+        ))
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(url)
+            .header("Cookie", &format!("session={}", self.session))
+            .call()?;
+        read_body(response)
+    }
+
+    /// Fetch the input of a puzzle.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(puzzle)?))
+    }
+
+    /// Fetch the description of a puzzle, converted to Markdown.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(puzzle)?)?;
+        let markdown = html_to_markdown(&html);
+
+        if markdown.is_empty() {
+            return Err(AocClientError::UnexpectedResponse(
+                "page does not contain a puzzle description.".into(),
+            ));
+        }
+
+        Ok(markdown)
+    }
+
+    /// Submit the answer to one part of a puzzle.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionResult, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(puzzle)?))
+            .header("Cookie", &format!("session={}", self.session))
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])?;
+
+        let text = html_to_markdown(&read_body(response)?);
+        SubmissionResult::from_response(&text).ok_or(AocClientError::UnexpectedResponse(text))
+    }
+}
+
+fn read_body(mut response: ureq::http::Response<ureq::Body>) -> Result<String, AocClientError> {
+    let status = response.status().as_u16();
+
+    if status != 200 {
+        return Err(AocClientError::BadStatus(status));
+    }
+
+    Ok(response.body_mut().read_to_string()?)
+}
+
+fn read_session() -> Result<String, AocClientError> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Ok(session.trim().into());
+        }
+    }
+
+    let path = env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".adventofcode.session"))
+        .ok_or(AocClientError::SessionNotFound)?;
+
+    match fs::read_to_string(path) {
+        Ok(session) if !session.trim().is_empty() => Ok(session.trim().into()),
+        _ => Err(AocClientError::SessionNotFound),
+    }
+}
+
+/// Write `contents` to `path`, creating the directory of its year if needed.
+fn write_data_file(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+/// Fetch the description of a puzzle, store it in `data/puzzles` and print it.
+pub fn read(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let description = AocClient::from_env()?.puzzle(puzzle)?;
+    write_data_file(&puzzle.data_path("puzzles", "md"), &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+/// Fetch the input and description of a puzzle and store them in `data/`.
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    write_data_file(&puzzle_path, &client.puzzle(puzzle)?)?;
+    write_data_file(&input_path, &client.input(puzzle)?)?;
+
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+pub fn submit(
+    puzzle: PuzzleId,
+    part: u8,
+    answer: &str,
+) -> Result<SubmissionResult, AocClientError> {
+    AocClient::from_env()?.submit(puzzle, part, answer)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    use super::{parse_wait, AocClient, AocClientError, SubmissionResult};
+    use crate::puzzle;
+
+    /// Serve a single request with `status` and `body`, sending the raw request to the receiver.
+    fn mock_server(status: u16, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            sender.send(request).unwrap();
+        });
+
+        (base_url, receiver)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (base_url, requests) = mock_server(200, "1 2\n3 4\n");
+        let client = AocClient::new(base_url, "secret", Some(2024));
+
+        assert_eq!(client.input(puzzle!(5)).unwrap(), "1 2\n3 4\n");
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/5/input HTTP/1.1\r\n"));
+        assert!(request.contains("session=secret"));
+    }
+
+    #[test]
+    fn sends_user_agent() {
+        let (base_url, requests) = mock_server(200, "1\n");
+        let client = AocClient::new(&base_url, "secret", Some(2024));
+        client.input(puzzle!(5)).unwrap();
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.contains("user-agent: github.com/bohdanqq/aoc-2024\r\n"));
+
+        let (base_url, requests) = mock_server(200, "1\n");
+        let client =
+            AocClient::new(base_url, "secret", Some(2024)).with_user_agent("me@example.com");
+        client.input(puzzle!(5)).unwrap();
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.contains("user-agent: me@example.com\r\n"));
+    }
+
+    #[test]
+    fn uses_year_of_puzzle() {
+        let (base_url, requests) = mock_server(200, "1\n");
+        let client = AocClient::new(base_url, "secret", Some(2024));
+
+        client.input(puzzle!(2015 / 3)).unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2015/day/3/input HTTP/1.1\r\n"));
+
+        let client = AocClient::new("http://127.0.0.1:1", "secret", None);
+        assert!(matches!(
+            client.input(puzzle!(3)),
+            Err(AocClientError::YearNotSet)
+        ));
+    }
+
+    #[test]
+    fn fetches_puzzle() {
+        let (base_url, _) = mock_server(
+            200,
+            "<html><main><article class=\"day-desc\"><h2>--- Day 1: Test ---</h2><p>Hi.</p></article></main></html>",
+        );
+        let client = AocClient::new(base_url, "secret", Some(2024));
+
+        assert_eq!(
+            client.puzzle(puzzle!(1)).unwrap(),
+            "## --- Day 1: Test ---\n\nHi.\n"
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, requests) = mock_server(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too high. Please wait one minute before trying again.</p></article></main>",
+        );
+        let client = AocClient::new(base_url, "secret", Some(2024));
+
+        assert_eq!(
+            client.submit(puzzle!(12), 2, "4711").unwrap(),
+            SubmissionResult::TooHigh
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2024/day/12/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("level=2&answer=4711"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (base_url, _) = mock_server(404, "Not Found");
+        let client = AocClient::new(base_url, "secret", Some(2024));
+
+        assert!(matches!(
+            client.input(puzzle!(25)),
+            Err(AocClientError::BadStatus(404))
+        ));
+    }
+
+    #[test]
+    fn parses_submission_results() {
+        let parse = SubmissionResult::from_response;
+
+        assert_eq!(
+            parse("That's the right answer! You are one gold star closer."),
+            Some(SubmissionResult::Correct)
+        );
+        assert_eq!(
+            parse("That's not the right answer. If you're stuck, ..."),
+            Some(SubmissionResult::Incorrect)
+        );
+        assert_eq!(
+            parse("That's not the right answer; your answer is too low."),
+            Some(SubmissionResult::TooLow)
+        );
+        assert_eq!(
+            parse(
+                "You gave an answer too recently. You have 1m 5s left to wait. [Return to Day 1]"
+            ),
+            Some(SubmissionResult::RateLimited(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            parse("You don't seem to be solving the right level. Did you already complete it?"),
+            Some(SubmissionResult::WrongLevel)
+        );
+        assert_eq!(parse("Something else entirely."), None);
+    }
+
+    #[test]
+    fn parses_waits() {
+        assert_eq!(
+            parse_wait("You have 37s left to wait."),
+            Some(Duration::from_secs(37))
+        );
+        assert_eq!(
+            parse_wait("You have 2m 0s left to wait."),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(parse_wait("You have a while left to wait."), None);
+    }
+}
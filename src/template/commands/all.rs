@@ -1,5 +1,15 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{all_puzzles, run_multi::run_multi};
+
+pub fn handle(is_release: bool, in_process: bool, jobs: usize, timeout: Option<Duration>) {
+    run_multi(
+        &all_puzzles().into_iter().collect(),
+        is_release,
+        false,
+        in_process,
+        jobs,
+        timeout,
+        false,
+    );
 }
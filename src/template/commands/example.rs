@@ -0,0 +1,105 @@
+use std::{fs, path::Path, process};
+
+use crate::template::{
+    examples::{format_expected, EXPECTED_EXTENSION},
+    extract, PuzzleId, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Number of lines shown of every code block when listing them.
+const PREVIEW_LINES: usize = 3;
+
+fn write_file(path: &Path, contents: &str) {
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, contents));
+
+    if let Err(e) = result {
+        eprintln!("Failed to write \"{}\": {e}", path.display());
+        process::exit(1);
+    }
+}
+
+/// Extract the example of a puzzle and its answers from the description in `data/puzzles`.
+/// `block` selects the code block to use as example, starting at 1. Defaults to the first block.
+pub fn handle(puzzle: PuzzleId, block: Option<usize>, overwrite: bool) {
+    let description_path = puzzle.data_path("puzzles", "md");
+    let description = match fs::read_to_string(&description_path) {
+        Ok(description) => description,
+        Err(e) => {
+            eprintln!(
+                "Failed to read \"{}\": {e}. Type `cargo download {puzzle}` to download the puzzle.",
+                description_path.display()
+            );
+            process::exit(1);
+        }
+    };
+
+    let blocks = extract::code_blocks(&description);
+
+    if blocks.is_empty() {
+        eprintln!("The description of puzzle {puzzle} contains no code blocks.");
+        process::exit(1);
+    }
+
+    println!("Code blocks in \"{}\":", description_path.display());
+    for (i, block) in blocks.iter().enumerate() {
+        let lines: Vec<&str> = block.lines().collect();
+        let unit = if lines.len() == 1 { "line" } else { "lines" };
+        println!("{ANSI_BOLD}[{}]{ANSI_RESET} {} {unit}", i + 1, lines.len());
+        for line in lines.iter().take(PREVIEW_LINES) {
+            println!("    {line}");
+        }
+        if lines.len() > PREVIEW_LINES {
+            println!("    ...");
+        }
+    }
+
+    let index = block.unwrap_or(1);
+    let Some(example) = index.checked_sub(1).and_then(|i| blocks.get(i)) else {
+        eprintln!("There is no code block {index}.");
+        process::exit(1);
+    };
+
+    println!("Using block {index}, select another one with `--block <n>`.");
+
+    let answers = extract::answers(&description);
+    if answers.is_empty() {
+        println!("No highlighted answers found.");
+    }
+    for (part, answer) in &answers {
+        println!("Part {part}: {ANSI_BOLD}{answer}{ANSI_RESET}");
+    }
+
+    let example_path = puzzle.data_path("examples", "txt");
+    let expected_path = example_path.with_extension(EXPECTED_EXTENSION);
+
+    // the empty example created by `cargo scaffold` may always be replaced.
+    let is_filled = |path: &Path| fs::metadata(path).is_ok_and(|m| m.len() > 0);
+    if !overwrite {
+        if let Some(path) = [&example_path, &expected_path]
+            .into_iter()
+            .find(|path| is_filled(path))
+        {
+            eprintln!(
+                "\"{}\" exists already, pass `--overwrite` to replace it.",
+                path.display()
+            );
+            process::exit(1);
+        }
+    }
+
+    println!("---");
+
+    write_file(&example_path, example);
+    println!("Wrote example to \"{}\".", example_path.display());
+
+    if !answers.is_empty() {
+        write_file(&expected_path, &format_expected(&answers));
+        println!("Wrote expected answers to \"{}\".", expected_path.display());
+        println!(
+            "🎄 Type `cargo test --bin {}` to check your solution against the example.",
+            puzzle.bin_name()
+        );
+    }
+}
@@ -1,15 +1,16 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::{
+    templates::{self, Variables},
+    PuzzleId,
+};
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +20,12 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    // puzzles of other years are stored in a directory per year.
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,53 +33,61 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Create an empty data file, unless it exists already, e.g. because the input was downloaded.
+fn create_data_file(path: &Path, kind: &str) {
+    if path.exists() {
+        println!("Kept existing {kind} file \"{}\"", path.display());
+        return;
+    }
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
+    match create_file(path) {
+        Ok(_) => {
+            println!("Created empty {kind} file \"{}\"", path.display());
+        }
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("Failed to create {kind} file: {e}");
             process::exit(1);
         }
-    };
+    }
+}
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
+/// Scaffold the solution of a puzzle from `template`, or from the default template if `None`.
+pub fn handle(puzzle: PuzzleId, overwrite: bool, template: Option<&str>) {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = puzzle.bin_path();
+
+    let template = match templates::load(template.unwrap_or(templates::DEFAULT_TEMPLATE)) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("Failed to load template: {e}");
             process::exit(1);
         }
-    }
+    };
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
+    let mut file = match safe_create_file(Path::new(&module_path), overwrite) {
+        Ok(file) => file,
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            eprintln!("Failed to create module file: {e}");
             process::exit(1);
         }
-    }
+    };
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    let contents = templates::render(&template, &Variables::collect(puzzle));
+
+    match file.write_all(contents.as_bytes()) {
+        Ok(()) => {
+            println!("Created module file \"{}\"", &module_path);
         }
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to write module contents: {e}");
             process::exit(1);
         }
     }
 
+    create_data_file(&input_path, "input");
+    create_data_file(&example_path, "example");
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("🎄 Type `cargo solve {puzzle}` to run your solution.");
 }
@@ -1,9 +1,16 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{input::Input, PuzzleId};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: &Input,
+    params: &[String],
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -17,6 +24,12 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     }
 
     cmd_args.push("--".to_string());
+    cmd_args.extend(input.to_args());
+
+    for param in params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param.clone());
+    }
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
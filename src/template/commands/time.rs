@@ -1,19 +1,75 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
+use crate::template::compare::{self, DEFAULT_THRESHOLD_PERCENT};
+use crate::template::history::{History, Run};
+use crate::template::report::{self, Format};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_puzzles, PuzzleId};
+
+/// Options of the `time` command.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Bench a single day instead of all days that have not been benched yet.
+    pub day: Option<PuzzleId>,
+    /// Bench all days, including the ones that have been benched before.
+    pub all: bool,
+    /// Store the timings in `data/timings.json` and render them in every [`Options::formats`].
+    pub store: bool,
+    /// Report formats to render when storing, defaults to the readme table.
+    pub formats: Vec<Format>,
+    /// Compare the timings against the stored ones instead of storing them.
+    pub compare: bool,
+    /// Tolerated relative slowdown in percent, see [`compare::compare`].
+    pub threshold: Option<f64>,
+    pub in_process: bool,
+    pub timeout: Option<Duration>,
+    /// Also measure the heap usage of every part with dhat.
+    pub memory: bool,
+    /// Print how the timings of a day evolved across stored runs instead of benching.
+    pub history: Option<PuzzleId>,
+}
+
+pub fn handle(options: &Options) {
+    let Options {
+        day,
+        all: run_all,
+        store,
+        ref formats,
+        compare,
+        threshold,
+        in_process,
+        timeout,
+        memory,
+        history,
+    } = *options;
+
+    if let Some(day) = history {
+        match History::read_from_file() {
+            Ok(history) => println!("{}", history.report(day)),
+            Err(e) => {
+                eprintln!("failed to read benchmark history: {e}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                all_puzzles().into_iter().collect()
+            } else if compare {
+                // when comparing, only days with a stored baseline are of interest.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_puzzles()
+                    .into_iter()
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,16 +77,43 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, in_process, 1, timeout, memory)
+        .timings
+        .unwrap();
+
+    if compare {
+        let threshold = threshold.unwrap_or(DEFAULT_THRESHOLD_PERCENT);
+        let comparisons = compare::compare(&stored_timings, &timings, threshold);
+
+        println!();
+        compare::print_report(&comparisons, threshold);
+
+        if compare::has_regression(&comparisons) {
+            eprintln!("Performance regression detected.");
+            process::exit(1);
+        }
+
+        return;
+    }
 
     if store {
+        if let Err(e) = History::append(&Run::capture(&timings)) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
+        let formats = if formats.is_empty() {
+            &[Format::Readme][..]
+        } else {
+            formats
+        };
+
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
+        match report::write(&merged_timings, formats) {
+            Ok(paths) => {
+                println!("Stored updated benchmarks in {}.", paths.join(", "));
             }
             Err(_) => {
                 eprintln!("Failed to store updated benchmarks.");
@@ -0,0 +1,59 @@
+use std::process;
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::run_multi::run_multi;
+use crate::template::{all_puzzles, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(accept: bool) {
+    let mut answers = Answers::read_from_file();
+    let run = run_multi(
+        &all_puzzles().into_iter().collect(),
+        true,
+        false,
+        false,
+        1,
+        None,
+        false,
+    );
+
+    let mut failed = false;
+    let mut accepted = false;
+
+    println!("\n{ANSI_BOLD}Verification{ANSI_RESET}");
+
+    for day_run in &run.days {
+        let day = &day_run.day;
+        let verdicts = answers.verify(*day, &day_run.reports);
+
+        for (part, verdict) in verdicts {
+            let status = match verdict {
+                Verdict::Pass => "PASS".to_string(),
+                Verdict::Fail { expected, actual } => {
+                    failed = true;
+                    let actual = actual.unwrap_or_else(|| "✖".into());
+                    format!("FAIL (expected {expected}, got {actual})")
+                }
+                Verdict::Missing { actual } if accept => {
+                    answers.set(*day, part, &actual);
+                    accepted = true;
+                    format!("ACCEPTED ({actual})")
+                }
+                Verdict::Missing { actual } => format!("MISSING (got {actual})"),
+            };
+
+            println!("Day {day} Part {part}: {status}");
+        }
+    }
+
+    if accepted {
+        if let Err(e) = answers.store_file() {
+            eprintln!("Failed to store answers: {e}");
+            process::exit(1);
+        }
+        println!("Stored accepted answers.");
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
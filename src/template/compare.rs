@@ -0,0 +1,223 @@
+/// Module that diffs fresh benchmark results against stored timings to detect regressions.
+use std::time::Duration;
+
+use crate::template::runner::{phase_label, PARSE_PART};
+use crate::template::timings::{PartStats, Timings};
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+/// Default relative slowdown (in percent) after which a part counts as regressed.
+pub const DEFAULT_THRESHOLD_PERCENT: f64 = 10.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    Improved,
+    Unchanged,
+    Regressed,
+    /// Either the stored or the fresh timing has no stats for this part.
+    Missing,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartComparison {
+    pub day: PuzzleId,
+    pub part: u8,
+    pub stored: Option<PartStats>,
+    pub current: Option<PartStats>,
+    pub verdict: Verdict,
+}
+
+impl PartComparison {
+    /// Relative change of the median runtime, e.g. `0.1` for a 10% slowdown.
+    pub fn change(&self) -> Option<f64> {
+        match (self.stored, self.current) {
+            (Some(stored), Some(current)) if stored.median_nanos > 0_f64 => {
+                Some(current.median_nanos / stored.median_nanos - 1_f64)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Judge a single part. Medians are compared so that single outliers do not trigger a verdict.
+/// A slowdown only counts as a regression if it exceeds `threshold` _and_ lies outside the stored
+/// sample distribution (i.e. the new median is above the stored p95).
+fn judge(stored: Option<PartStats>, current: Option<PartStats>, threshold: f64) -> Verdict {
+    let (Some(stored), Some(current)) = (stored, current) else {
+        return Verdict::Missing;
+    };
+
+    if stored.median_nanos <= 0_f64 {
+        return Verdict::Missing;
+    }
+
+    let change = current.median_nanos / stored.median_nanos - 1_f64;
+
+    if change > threshold && current.median_nanos > stored.p95_nanos {
+        Verdict::Regressed
+    } else if change < -threshold && current.p95_nanos < stored.median_nanos {
+        Verdict::Improved
+    } else {
+        Verdict::Unchanged
+    }
+}
+
+/// Compare every part in `current` with its counterpart in `stored`.
+/// `threshold_percent` is the tolerated relative slowdown, e.g. `10.0` for 10%.
+pub fn compare(stored: &Timings, current: &Timings, threshold_percent: f64) -> Vec<PartComparison> {
+    let threshold = threshold_percent / 100_f64;
+
+    current
+        .data
+        .iter()
+        .flat_map(|timing| {
+            let baseline = stored.data.iter().find(|t| t.day == timing.day);
+
+            [
+                (
+                    PARSE_PART,
+                    baseline.and_then(|t| t.parse_stats),
+                    timing.parse_stats,
+                ),
+                (
+                    1,
+                    baseline.and_then(|t| t.part_1_stats),
+                    timing.part_1_stats,
+                ),
+                (
+                    2,
+                    baseline.and_then(|t| t.part_2_stats),
+                    timing.part_2_stats,
+                ),
+            ]
+            .into_iter()
+            .filter(|(_, stored, current)| stored.is_some() || current.is_some())
+            .map(|(part, stored, current)| PartComparison {
+                day: timing.day,
+                part,
+                stored,
+                current,
+                verdict: judge(stored, current, threshold),
+            })
+        })
+        .collect()
+}
+
+pub fn has_regression(comparisons: &[PartComparison]) -> bool {
+    comparisons.iter().any(|c| c.verdict == Verdict::Regressed)
+}
+
+fn format_nanos(stats: Option<PartStats>) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    stats.map_or_else(
+        || "-".into(),
+        |s| format!("{:.1?}", Duration::from_nanos(s.median_nanos as u64)),
+    )
+}
+
+pub fn print_report(comparisons: &[PartComparison], threshold_percent: f64) {
+    println!("{ANSI_BOLD}Comparison of medians (threshold: {threshold_percent}%){ANSI_RESET}");
+
+    for comparison in comparisons {
+        let change = comparison
+            .change()
+            .map_or_else(String::new, |c| format!(" ({:+.1}%)", c * 100_f64));
+
+        let verdict = match comparison.verdict {
+            Verdict::Improved => "improved",
+            Verdict::Unchanged => "ok",
+            Verdict::Regressed => "REGRESSION",
+            Verdict::Missing => "no baseline",
+        };
+
+        println!(
+            "Day {} {}: {} -> {}{change} {verdict}",
+            comparison.day,
+            phase_label(comparison.part),
+            format_nanos(comparison.stored),
+            format_nanos(comparison.current),
+        );
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, has_regression, Verdict};
+    use crate::{
+        puzzle,
+        template::timings::{PartStats, Timing, Timings},
+    };
+
+    fn stats(median_nanos: f64, p95_nanos: f64) -> PartStats {
+        PartStats {
+            samples: 100.0,
+            mean_nanos: median_nanos,
+            min_nanos: median_nanos,
+            median_nanos,
+            p95_nanos,
+            stddev_nanos: 0.0,
+        }
+    }
+
+    fn timings(part_1: Option<PartStats>, part_2: Option<PartStats>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: puzzle!(1),
+                part_1: part_1.map(|_| "1ns".into()),
+                part_2: part_2.map(|_| "1ns".into()),
+                part_1_stats: part_1,
+                part_2_stats: part_2,
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn flags_slowdowns_beyond_threshold() {
+        let stored = timings(Some(stats(100.0, 110.0)), Some(stats(100.0, 110.0)));
+        let current = timings(Some(stats(105.0, 115.0)), Some(stats(150.0, 160.0)));
+        let res = compare(&stored, &current, 10.0);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].verdict, Verdict::Unchanged);
+        assert_eq!(res[1].verdict, Verdict::Regressed);
+        assert!(has_regression(&res));
+    }
+
+    #[test]
+    fn ignores_slowdowns_within_stored_variance() {
+        let stored = timings(Some(stats(100.0, 200.0)), None);
+        let current = timings(Some(stats(150.0, 210.0)), None);
+        let res = compare(&stored, &current, 10.0);
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].verdict, Verdict::Unchanged);
+        assert!(!has_regression(&res));
+    }
+
+    #[test]
+    fn detects_improvements() {
+        let stored = timings(Some(stats(100.0, 110.0)), None);
+        let current = timings(Some(stats(50.0, 60.0)), None);
+        let res = compare(&stored, &current, 10.0);
+        assert_eq!(res[0].verdict, Verdict::Improved);
+    }
+
+    #[test]
+    fn handles_missing_baseline() {
+        let stored = Timings::default();
+        let current = timings(Some(stats(100.0, 110.0)), Some(stats(100.0, 110.0)));
+        let res = compare(&stored, &current, 10.0);
+        assert!(res.iter().all(|c| c.verdict == Verdict::Missing));
+        assert!(!has_regression(&res));
+    }
+
+    #[test]
+    fn compares_parse_phase() {
+        let mut stored = timings(Some(stats(100.0, 110.0)), None);
+        stored.data[0].parse_stats = Some(stats(100.0, 110.0));
+        let mut current = timings(Some(stats(100.0, 110.0)), None);
+        current.data[0].parse_stats = Some(stats(200.0, 210.0));
+        let res = compare(&stored, &current, 10.0);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].part, 0);
+        assert_eq!(res[0].verdict, Verdict::Regressed);
+    }
+}
@@ -0,0 +1,313 @@
+/// Runs a day's solution against every example that has known answers.
+///
+/// Every `data/examples/<day>*.txt` file (e.g. `01.txt`, `01-2.txt`, `01-large.txt`) with a sidecar
+/// `<name>.expected` file is a case. The sidecar lists the expected answer of each part per line:
+///
+/// ```text
+/// part_1: 11
+/// part_2: 31
+/// ```
+///
+/// The [`solution!`](crate::solution) macro generates an `example_cases` test that calls [`run`],
+/// so adding an example is a matter of dropping two files into `data/examples`.
+use std::{
+    env, fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+use crate::template::{registry::Solution, runner::PartOutcome, Day, PuzzleId};
+
+/// File extension of the expected answers of an example.
+pub const EXPECTED_EXTENSION: &str = "expected";
+
+/// An example input with its expected answers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Case {
+    /// File name of the example, e.g. `01-2.txt`.
+    pub name: String,
+    pub input: String,
+    /// Expected answers by part.
+    pub expected: Vec<(u8, String)>,
+}
+
+/// Parse the contents of an `.expected` file. Empty lines and lines starting with `#` are ignored.
+pub fn parse_expected(s: &str) -> Result<Vec<(u8, String)>, String> {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("expected `part_<n>: <answer>`, got `{line}`."))?;
+
+            let part = key
+                .trim()
+                .strip_prefix("part_")
+                .and_then(|part| part.parse().ok())
+                .filter(|part| matches!(part, 1 | 2))
+                .ok_or_else(|| format!("unknown part `{}`.", key.trim()))?;
+
+            Ok((part, value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Format expected answers as the contents of an `.expected` file, see [`parse_expected`].
+pub fn format_expected(expected: &[(u8, String)]) -> String {
+    expected
+        .iter()
+        .map(|(part, answer)| format!("part_{part}: {answer}\n"))
+        .collect()
+}
+
+/// Whether `file_name` names an example of the given day, e.g. `01.txt` or `01-2.txt` but not `011.txt`.
+fn is_example_of(file_name: &str, day: Day) -> bool {
+    file_name
+        .strip_suffix(".txt")
+        .and_then(|stem| stem.strip_prefix(&day.to_string()))
+        .is_some_and(|rest| !rest.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Collect the cases of a day from `dir`, sorted by file name. Examples without expected answers are skipped.
+/// A case that cannot be read is returned as an error, so it does not hide the other cases.
+pub fn discover_in(dir: &Path, day: Day) -> Vec<Result<Case, String>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| is_example_of(name, day))
+        .collect();
+
+    names.sort();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let expected_path = dir.join(&name).with_extension(EXPECTED_EXTENSION);
+            let expected = fs::read_to_string(&expected_path).ok()?;
+
+            let case = fs::read_to_string(dir.join(&name))
+                .map_err(|e| format!("{name}: {e}"))
+                .and_then(|input| {
+                    let expected = parse_expected(&expected)
+                        .map_err(|e| format!("{}: {e}", expected_path.display()))?;
+                    Ok(Case {
+                        name,
+                        input,
+                        expected,
+                    })
+                });
+
+            Some(case)
+        })
+        .collect()
+}
+
+/// Collect the cases of a puzzle from `data/examples`.
+pub fn discover(puzzle: PuzzleId) -> Result<Vec<Result<Case, String>>, String> {
+    let dir = env::current_dir()
+        .map_err(|e| e.to_string())?
+        .join(puzzle.data_dir("examples"));
+
+    Ok(discover_in(&dir, puzzle.day))
+}
+
+/// Check a single case. Returns a line per part whose answer did not match.
+/// Expected answers of parts the solution does not register are ignored.
+pub fn check(solution: &Solution, case: &Case) -> Vec<String> {
+    case.expected
+        .iter()
+        .filter_map(|(part, expected)| {
+            let (_, func) = solution.parts.iter().find(|(p, _)| p == part)?;
+
+            let actual = panic::catch_unwind(AssertUnwindSafe(|| {
+                func(&case.input).map(|answer| answer.to_string())
+            }));
+
+            let failure = match actual {
+                Ok(PartOutcome::Solved(actual)) if actual == *expected => return None,
+                Ok(PartOutcome::Solved(actual)) => format!("expected `{expected}`, got `{actual}`"),
+                Ok(PartOutcome::Unsolved) => format!("expected `{expected}`, got no answer"),
+                Ok(PartOutcome::Failed(e)) => {
+                    format!("expected `{expected}`, but the solution failed: {e}")
+                }
+                Err(_) => format!("expected `{expected}`, but the solution panicked"),
+            };
+
+            Some(format!("{} part {part}: {failure}", case.name))
+        })
+        .collect()
+}
+
+/// Check all cases, returning a line per case that could not be read and per part that did not match.
+pub fn failures(solution: &Solution, cases: &[Result<Case, String>]) -> Vec<String> {
+    cases
+        .iter()
+        .flat_map(|case| match case {
+            Ok(case) => check(solution, case),
+            Err(e) => vec![e.clone()],
+        })
+        .collect()
+}
+
+/// Run all cases of a solution, panicking with every failure if any of them fails.
+pub fn run(solution: &Solution) {
+    let cases = discover(solution.day).unwrap_or_else(|e| panic!("{e}"));
+    let failures = failures(solution, &cases);
+
+    assert!(
+        failures.is_empty(),
+        "{} failures in {} example cases:\n{}",
+        failures.len(),
+        cases.len(),
+        failures.join("\n")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        check, discover_in, failures, format_expected, is_example_of, parse_expected, Case,
+    };
+    use crate::{
+        day, puzzle,
+        template::registry::{erase, Solution},
+    };
+    use std::{env, fs, process};
+
+    fn solution() -> Solution {
+        Solution {
+            day: puzzle!(1),
+            parts: vec![
+                (1, erase(|input: &str| Some(input.lines().count()))),
+                (
+                    2,
+                    erase(|input: &str| input.lines().next().map(String::from)),
+                ),
+            ],
+            run: |_, _| {},
+        }
+    }
+
+    #[test]
+    fn parses_expected_answers() {
+        let res = parse_expected("# example 2\npart_1: 11\n\n part_2 : 6,1 \n").unwrap();
+        assert_eq!(res, vec![(1, "11".into()), (2, "6,1".into())]);
+        assert!(parse_expected("part_3: 1").is_err());
+        assert!(parse_expected("11").is_err());
+    }
+
+    #[test]
+    fn formats_expected_answers() {
+        let expected = vec![(1, "11".to_string()), (2, "6,1".to_string())];
+        let formatted = format_expected(&expected);
+
+        assert_eq!(formatted, "part_1: 11\npart_2: 6,1\n");
+        assert_eq!(parse_expected(&formatted).unwrap(), expected);
+    }
+
+    #[test]
+    fn matches_example_names() {
+        assert!(is_example_of("01.txt", day!(1)));
+        assert!(is_example_of("01-2.txt", day!(1)));
+        assert!(is_example_of("01_large.txt", day!(1)));
+        assert!(!is_example_of("011.txt", day!(1)));
+        assert!(!is_example_of("02.txt", day!(1)));
+        assert!(!is_example_of("01.expected", day!(1)));
+    }
+
+    #[test]
+    fn discovers_cases_with_expected_answers() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("01.txt"), "a\nb").unwrap();
+        fs::write(dir.join("01.expected"), "part_1: 2").unwrap();
+        fs::write(dir.join("01-2.txt"), "c").unwrap();
+        fs::write(dir.join("01-3.txt"), "e").unwrap();
+        fs::write(dir.join("01-3.expected"), "part_3: 1").unwrap();
+        fs::write(dir.join("02.txt"), "d").unwrap();
+        fs::write(dir.join("02.expected"), "part_1: 1").unwrap();
+
+        let cases = discover_in(&dir, day!(1));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(cases.len(), 2);
+        assert!(cases[0]
+            .as_ref()
+            .is_err_and(|e| e.contains("unknown part `part_3`")));
+        assert_eq!(
+            cases[1],
+            Ok(Case {
+                name: "01.txt".into(),
+                input: "a\nb".into(),
+                expected: vec![(1, "2".into())],
+            })
+        );
+    }
+
+    #[test]
+    fn checks_cases() {
+        let case = Case {
+            name: "01-2.txt".into(),
+            input: "x\ny".into(),
+            expected: vec![(1, "2".into()), (2, "x".into())],
+        };
+        assert!(check(&solution(), &case).is_empty());
+
+        let case = Case {
+            expected: vec![(1, "3".into())],
+            ..case
+        };
+        assert_eq!(
+            check(&solution(), &case),
+            vec!["01-2.txt part 1: expected `3`, got `2`".to_string()]
+        );
+    }
+
+    #[test]
+    fn reports_failed_parts() {
+        let solution = Solution {
+            day: puzzle!(1),
+            parts: vec![(1, erase(|input: &str| input.trim().parse::<u32>()))],
+            run: |_, _| {},
+        };
+        let case = Case {
+            name: "01.txt".into(),
+            input: "x".into(),
+            expected: vec![(1, "2".into())],
+        };
+        assert_eq!(
+            check(&solution, &case),
+            vec!["01.txt part 1: expected `2`, but the solution failed: invalid digit found in string".to_string()]
+        );
+    }
+
+    #[test]
+    fn reports_every_failure() {
+        let case = |name: &str, expected: &str| Case {
+            name: name.into(),
+            input: "x\ny".into(),
+            expected: vec![(1, expected.into())],
+        };
+        let cases = vec![
+            Ok(case("01.txt", "3")),
+            Err("01-2.expected: unknown part `part_3`.".into()),
+            Ok(case("01-3.txt", "2")),
+            Ok(case("01-4.txt", "1")),
+        ];
+
+        assert_eq!(
+            failures(&solution(), &cases),
+            vec![
+                "01.txt part 1: expected `3`, got `2`".to_string(),
+                "01-2.expected: unknown part `part_3`.".to_string(),
+                "01-4.txt part 1: expected `1`, got `2`".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,104 @@
+/// Module that extracts examples from puzzle descriptions, as stored by `cargo download` in
+/// `data/puzzles`.
+///
+/// Examples are the code blocks of the description. The answers to the examples are highlighted as
+/// emphasized code, e.g. *`11`*. The last highlighted code of each part is taken as its answer.
+use crate::regex;
+
+/// The contents of all fenced code blocks, in order. Every block ends with a line break.
+pub fn code_blocks(description: &str) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut current: Option<String> = None;
+
+    for line in description.lines() {
+        if line.starts_with("```") {
+            match current.take() {
+                Some(block) => blocks.push(block),
+                None => current = Some(String::new()),
+            }
+        } else if let Some(block) = &mut current {
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+
+    blocks
+}
+
+/// The highlighted answers of the examples by part. Parts without a highlighted answer, e.g. part
+/// two before it is unlocked, are left out.
+pub fn answers(description: &str) -> Vec<(u8, String)> {
+    // the description of each part starts with a heading, e.g. `## --- Part Two ---`.
+    let mut parts: Vec<String> = vec![];
+
+    for line in description.lines() {
+        if line.starts_with("## ") {
+            parts.push(String::new());
+        } else if let Some(part) = parts.last_mut() {
+            part.push_str(line);
+            part.push('\n');
+        }
+    }
+
+    parts
+        .iter()
+        .zip(1..=2)
+        .filter_map(|(text, part)| {
+            let answer = regex(r"\*`([^`\n]+)`\*")
+                .captures_iter(text)
+                .last()?
+                .get(1)?
+                .as_str()
+                .trim()
+                .to_string();
+            Some((part, answer))
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answers, code_blocks};
+
+    const DESCRIPTION: &str = "## --- Day 1: Historian Hysteria ---
+
+For example:
+
+```
+3   4
+4   3
+```
+
+Pair up `3` and *`4`*, a *total* distance of *`11`*!
+
+Another block:
+
+```
+x
+```
+
+## --- Part Two ---
+
+The similarity score is *`31`* (`9 + 4`).
+";
+
+    #[test]
+    fn extracts_code_blocks() {
+        assert_eq!(code_blocks(DESCRIPTION), vec!["3   4\n4   3\n", "x\n"]);
+        assert!(code_blocks("no code").is_empty());
+    }
+
+    #[test]
+    fn extracts_answers() {
+        assert_eq!(
+            answers(DESCRIPTION),
+            vec![(1, "11".to_string()), (2, "31".to_string())]
+        );
+
+        let part_one = DESCRIPTION.split("## --- Part Two").next().unwrap();
+        assert_eq!(answers(part_one), vec![(1, "11".to_string())]);
+        assert!(answers("## --- Day 1: Test ---\n\nNo answer.").is_empty());
+    }
+}
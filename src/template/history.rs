@@ -0,0 +1,388 @@
+/// Module that keeps an append-only history of benchmark runs.
+///
+/// `data/timings.json` only holds the latest timing of every day. Every stored run is additionally
+/// appended as a JSON line to `data/timings-history.jsonl`, together with the environment it ran in,
+/// so the runtime of a part can be traced across commits.
+use std::{
+    collections::HashMap,
+    env, fs,
+    fs::OpenOptions,
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use chrono::DateTime;
+use tinyjson::JsonValue;
+
+use crate::template::{
+    runner::{phase_label, PARSE_PART},
+    timings::{PartStats, Timing, Timings},
+    PuzzleId, ANSI_BOLD, ANSI_RESET,
+};
+
+static HISTORY_FILE_PATH: &str = "./data/timings-history.jsonl";
+
+/// Name of the environment variable that overrides the machine id, which defaults to the host name.
+pub const MACHINE_ENV: &str = "AOC_MACHINE_ID";
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Abbreviated hash of the checked out commit, suffixed with `-dirty` if the tree had changes.
+    pub commit: Option<String>,
+    pub rustc: Option<String>,
+    pub machine: String,
+    pub timings: Vec<Timing>,
+}
+
+impl Run {
+    /// Capture the current environment for a run that produced `timings`.
+    pub fn capture(timings: &Timings) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        let is_dirty = || command_output("git", &["status", "--porcelain", "-uno"]).is_some();
+        let commit = command_output("git", &["rev-parse", "--short", "HEAD"]).map(|hash| {
+            if is_dirty() {
+                format!("{hash}-dirty")
+            } else {
+                hash
+            }
+        });
+
+        let machine = env::var(MACHINE_ENV)
+            .ok()
+            .filter(|id| !id.is_empty())
+            .or_else(|| command_output("hostname", &[]))
+            .unwrap_or_else(|| "unknown".into());
+
+        Run {
+            timestamp,
+            commit,
+            rustc: command_output("rustc", &["--version"]),
+            machine,
+            timings: timings.data.clone(),
+        }
+    }
+}
+
+/// Trimmed stdout of a successful command, `None` if it failed or printed nothing.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let stdout = stdout.trim();
+    (!stdout.is_empty()).then(|| stdout.to_string())
+}
+
+/// All recorded runs, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    /// Append a run to the history file.
+    pub fn append(run: &Run) -> Result<(), io::Error> {
+        let line = JsonValue::from(run)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(HISTORY_FILE_PATH)?;
+        writeln!(file, "{line}")
+    }
+
+    /// Read the history file. If not present, returns an empty history.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(HISTORY_FILE_PATH) {
+            Ok(content) => History::from_str(&content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Render how the runtime of every phase of `day` evolved. Changes compare medians, like
+    /// `--compare`, and are relative to the previous run on the same machine, since runs on
+    /// different machines are not comparable.
+    pub fn report(&self, day: PuzzleId) -> String {
+        let mut rows = vec![[
+            "Date".to_string(),
+            "Commit".to_string(),
+            "Machine".to_string(),
+            phase_label(PARSE_PART),
+            phase_label(1),
+            phase_label(2),
+        ]];
+
+        let mut previous: HashMap<&str, &Timing> = HashMap::new();
+
+        for run in &self.runs {
+            let Some(timing) = run.timings.iter().find(|t| t.day == day) else {
+                continue;
+            };
+
+            let before = previous.insert(&run.machine, timing);
+            let phase = |time: &Option<String>, stats: fn(&Timing) -> Option<PartStats>| {
+                let Some(time) = time else {
+                    return "-".to_string();
+                };
+
+                let change = before
+                    .and_then(stats)
+                    .zip(stats(timing))
+                    .filter(|(before, _)| before.median_nanos > 0_f64)
+                    .map(|(before, now)| {
+                        (now.median_nanos / before.median_nanos - 1_f64) * 100_f64
+                    });
+
+                match change {
+                    Some(change) => format!("{time} ({change:+.1}%)"),
+                    None => time.clone(),
+                }
+            };
+
+            rows.push([
+                format_timestamp(run.timestamp),
+                run.commit.clone().unwrap_or_else(|| "-".into()),
+                run.machine.clone(),
+                phase(&timing.parse, |t| t.parse_stats),
+                phase(&timing.part_1, |t| t.part_1_stats),
+                phase(&timing.part_2, |t| t.part_2_stats),
+            ]);
+        }
+
+        if rows.len() == 1 {
+            return format!("No benchmark history for day {day}.");
+        }
+
+        let widths: Vec<usize> = (0..rows[0].len())
+            .map(|i| rows.iter().map(|row| row[i].chars().count()).max().unwrap())
+            .collect();
+
+        let lines: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{cell:<width$}"))
+                    .collect();
+                cells.join("  ").trim_end().to_string()
+            })
+            .collect();
+
+        format!("{ANSI_BOLD}Day {day}{ANSI_RESET}\n{}", lines.join("\n"))
+    }
+}
+
+/// Format seconds since the unix epoch as UTC date and time, e.g. `2024-12-01 05:00`.
+fn format_timestamp(timestamp: u64) -> String {
+    i64::try_from(timestamp)
+        .ok()
+        .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+        .map_or_else(
+            || "-".into(),
+            |date| date.format("%Y-%m-%d %H:%M").to_string(),
+        )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Run> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Run) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "rustc".into(),
+            value
+                .rustc
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("machine".into(), JsonValue::String(value.machine.clone()));
+        map.insert(
+            "timings".into(),
+            JsonValue::Array(value.timings.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Run {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected run.timestamp to be a number.")?;
+
+        let optional_string = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or_else(|| format!("Expected run.{key} to be null or string.")),
+            _ => Ok(None),
+        };
+
+        let machine = json
+            .get("machine")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected run.machine to be a string.")?;
+
+        let timings = json
+            .get("timings")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected run.timings to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Run {
+            timestamp,
+            commit: optional_string("commit")?,
+            rustc: optional_string("rustc")?,
+            machine: machine.clone(),
+            timings,
+        })
+    }
+}
+
+impl FromStr for History {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let runs = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let json = JsonValue::from_str(line)
+                    .map_err(|_| format!("history line {} is not valid JSON.", i + 1))?;
+                Run::try_from(&json)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(History { runs })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, History, Run};
+    use crate::{
+        puzzle,
+        template::timings::{PartStats, Timing},
+    };
+    use tinyjson::JsonValue;
+
+    fn timing(part_1_nanos: f64) -> Timing {
+        Timing {
+            day: puzzle!(1),
+            part_1: Some(format!("{part_1_nanos:.1}ns")),
+            part_1_stats: Some(PartStats {
+                // a skewed mean, so the report must compare medians to show -25%.
+                mean_nanos: part_1_nanos + 1_000_f64,
+                median_nanos: part_1_nanos,
+                ..Default::default()
+            }),
+            total_nanos: part_1_nanos,
+            ..Default::default()
+        }
+    }
+
+    fn run(timestamp: u64, commit: &str, machine: &str, part_1_nanos: f64) -> Run {
+        Run {
+            timestamp,
+            commit: Some(commit.into()),
+            rustc: Some("rustc 1.83.0".into()),
+            machine: machine.into(),
+            timings: vec![timing(part_1_nanos)],
+        }
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_029_200), "2024-12-01 05:00");
+        assert_eq!(format_timestamp(951_825_600), "2000-02-29 12:00");
+    }
+
+    #[test]
+    fn roundtrips_runs() {
+        let mut history = run(1_733_029_200, "1a2b3c4", "laptop", 100.0);
+        history.rustc = None;
+
+        let line = JsonValue::from(&history).stringify().unwrap();
+        let parsed: History = format!("{line}\n\n{line}\n").parse().unwrap();
+
+        assert_eq!(parsed.runs.len(), 2);
+        assert_eq!(parsed.runs[0].commit.as_deref(), Some("1a2b3c4"));
+        assert_eq!(parsed.runs[0].rustc, None);
+        assert_eq!(parsed.runs[0].machine, "laptop");
+        assert_eq!(
+            parsed.runs[0].timings[0].part_1_stats.unwrap().median_nanos,
+            100.0
+        );
+    }
+
+    #[test]
+    fn reports_changes_per_machine() {
+        let history = History {
+            runs: vec![
+                run(1_733_029_200, "1a2b3c4", "laptop", 100.0),
+                run(1_733_032_800, "5d6e7f8", "desktop", 40.0),
+                run(1_733_036_400, "9a8b7c6", "laptop", 75.0),
+            ],
+        };
+
+        let report = history.report(puzzle!(1));
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with("Date"));
+        assert!(lines[2].contains("100.0ns") && !lines[2].contains('%'));
+        assert!(lines[3].contains("40.0ns  "));
+        assert!(lines[4].contains("9a8b7c6"));
+        assert!(lines[4].contains("75.0ns (-25.0%)"));
+
+        assert_eq!(
+            history.report(puzzle!(2)),
+            "No benchmark history for day 02."
+        );
+    }
+}
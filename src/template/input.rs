@@ -0,0 +1,148 @@
+/// Selects the input a solution is run against.
+///
+/// `cargo solve` forwards `--input <path>` and `--example [N]` to the solution binary, where the
+/// `main` generated by [`solution!`](crate::solution) resolves them via [`Input::from_args`].
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+};
+
+use crate::template::{read_file, read_file_part, PuzzleId};
+
+/// Path that selects stdin instead of a file, e.g. `cargo solve 1 --input -`.
+pub const STDIN_PATH: &str = "-";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Input {
+    /// The puzzle input at `data/inputs/<day>.txt`.
+    #[default]
+    Puzzle,
+    /// The example at `data/examples/<day>.txt`, or `data/examples/<day>-<N>.txt` if a number is set.
+    Example(Option<u8>),
+    /// An arbitrary file.
+    File(PathBuf),
+    Stdin,
+}
+
+impl Input {
+    /// Parse the input selection from command-line arguments. Unrelated arguments are ignored.
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let value_after = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .map(|i| args.get(i + 1).filter(|x| !x.starts_with("--")))
+        };
+
+        match (value_after("--input"), value_after("--example")) {
+            (Some(_), Some(_)) => Err("`--input` and `--example` cannot be combined.".into()),
+            (Some(None), None) => Err("`--input` expects a path.".into()),
+            (Some(Some(path)), None) if path == STDIN_PATH => Ok(Input::Stdin),
+            (Some(Some(path)), None) => Ok(Input::File(path.into())),
+            (None, Some(None)) => Ok(Input::Example(None)),
+            (None, Some(Some(n))) => n
+                .parse()
+                .map(|n| Input::Example(Some(n)))
+                .map_err(|_| format!("`--example` expects a number, got `{n}`.")),
+            (None, None) => Ok(Input::Puzzle),
+        }
+    }
+
+    /// Parse the input selection from the arguments of the current process, exiting on invalid arguments.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        Self::parse(&args).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    }
+
+    /// Arguments that select this input when passed to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Input::Puzzle => vec![],
+            Input::Example(None) => vec!["--example".into()],
+            Input::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+            Input::File(path) => vec!["--input".into(), path.to_string_lossy().into()],
+            Input::Stdin => vec!["--input".into(), STDIN_PATH.into()],
+        }
+    }
+
+    /// Read the selected input for the given puzzle.
+    #[must_use]
+    pub fn read(&self, puzzle: PuzzleId) -> String {
+        match self {
+            Input::Puzzle => read_file("inputs", puzzle),
+            Input::Example(None) => read_file("examples", puzzle),
+            Input::Example(Some(n)) => read_file_part("examples", puzzle, *n),
+            Input::File(path) => fs::read_to_string(path).unwrap_or_else(|e| {
+                eprintln!("could not open input file {}: {e}", path.display());
+                process::exit(1);
+            }),
+            Input::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .expect("could not read input from stdin");
+                input
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Input;
+
+    fn parse(args: &[&str]) -> Result<Input, String> {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        Input::parse(&args)
+    }
+
+    #[test]
+    fn parses_inputs() {
+        assert_eq!(parse(&["01"]), Ok(Input::Puzzle));
+        assert_eq!(parse(&["01", "--example"]), Ok(Input::Example(None)));
+        assert_eq!(
+            parse(&["01", "--example", "2"]),
+            Ok(Input::Example(Some(2)))
+        );
+        assert_eq!(
+            parse(&["01", "--example", "--time"]),
+            Ok(Input::Example(None))
+        );
+        assert_eq!(
+            parse(&["01", "--input", "in.txt", "--submit", "1"]),
+            Ok(Input::File("in.txt".into()))
+        );
+        assert_eq!(parse(&["01", "--input", "-"]), Ok(Input::Stdin));
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        assert!(parse(&["01", "--input"]).is_err());
+        assert!(parse(&["01", "--example", "x"]).is_err());
+        assert!(parse(&["01", "--input", "a.txt", "--example"]).is_err());
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for input in [
+            Input::Puzzle,
+            Input::Example(None),
+            Input::Example(Some(3)),
+            Input::File("data/other/01.txt".into()),
+            Input::Stdin,
+        ] {
+            let args = input.to_args();
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            assert_eq!(parse(&args), Ok(input));
+        }
+    }
+}
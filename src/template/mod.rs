@@ -1,14 +1,28 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
+pub mod input;
+pub mod params;
+pub mod registry;
 pub mod runner;
 
 pub use day::*;
+pub use puzzle_id::*;
 
+mod answers;
+mod compare;
 mod day;
+mod extract;
+mod history;
+mod part_report;
+mod puzzle_id;
 mod readme_benchmarks;
+pub mod report;
 mod run_multi;
+mod submissions;
+mod templates;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -17,52 +31,159 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: impl Into<PuzzleId>) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.into().data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<PuzzleId>, part: u8) -> String {
+    let puzzle = puzzle.into();
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(puzzle.data_dir(folder))
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// The first parameter is the day, prefixed by the year for puzzles outside of the main event,
+/// e.g. `solution!(2023/05)` (see [`PuzzleId`]).
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// The generated `main` reads the puzzle input, unless another input is selected via `--input <path>`
+/// or `--example [N]` (see [`input::Input`]).
+///
+/// Days that share parsing between both parts pass their parser as `parse = parse`, or as
+/// `try_parse = parse` if it returns a `Result`. The input is then parsed once, and the part functions
+/// receive a reference to the parsed input. The parse phase is timed and reported on its own.
+///
+/// Days with input-dependent parameters pass a struct declared with [`params!`](crate::params) as
+/// `params = Params`. The part functions then read the parameters of the current run via `params()`.
+///
+/// Also generates `__solution()`, which registers the parts for in-process execution (see [`registry`]),
+/// and the `example_cases` test, which checks every example with known answers (see [`examples`]).
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:literal / $day:literal $($rest:tt)*) => {
+        $crate::solution!(@id [Some($year)] $day $($rest)*);
+    };
+    ($day:literal $($rest:tt)*) => {
+        $crate::solution!(@id [None] $day $($rest)*);
+    };
+
+    (@id $year:tt $day:expr) => {
+        $crate::solution!(@impl $year $day, [], [], [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (@id $year:tt $day:expr, 1) => {
+        $crate::solution!(@impl $year $day, [], [], [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@id $year:tt $day:expr, 2) => {
+        $crate::solution!(@impl $year $day, [], [], [part_two, 2]);
+    };
+    (@id $year:tt $day:expr, params = $params:ty) => {
+        $crate::solution!(@impl $year $day, [$params], [], [part_one, 1] [part_two, 2]);
+    };
+    (@id $year:tt $day:expr, parse = $parse:expr $(, params = $params:ty)?) => {
+        $crate::solution!(@impl $year $day, [$($params)?], [parse $parse], [part_one, 1] [part_two, 2]);
+    };
+    (@id $year:tt $day:expr, try_parse = $parse:expr $(, params = $params:ty)?) => {
+        $crate::solution!(@impl $year $day, [$($params)?], [try_parse $parse], [part_one, 1] [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl [$year:expr] $day:expr, [$($params:ty)?], $parser:tt, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The current puzzle, i.e. the current day and the year of its event.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new($year, DAY);
+
+        $(
+        static __PARAMS: std::sync::OnceLock<$params> = std::sync::OnceLock::new();
+
+        /// Parameters of the current run. Tests use the example defaults.
+        fn params() -> &'static $params {
+            use $crate::template::params::PuzzleParams;
+            __PARAMS.get_or_init(|| if cfg!(test) { <$params>::example() } else { <$params>::real() })
+        }
+        )?
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        // unused when compiled as a module of the aggregate binary.
+        #[allow(dead_code)]
         fn main() {
+            let source = $crate::template::input::Input::from_args();
+            $( let _ = __PARAMS.set($crate::template::params::from_args::<$params>(&source)); )?
+            let input = source.read(PUZZLE);
+            #[cfg(feature = "dhat-heap")]
+            let _profiler = $crate::template::runner::dhat_profiler();
+            __run(&input, $crate::template::runner::unguarded);
+        }
+
+        /// Runs the parse phase, if any, and every part on `input`. Each phase is executed through `guard`.
+        #[allow(dead_code)]
+        fn __run(input: &str, guard: $crate::template::runner::Guard) {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $crate::solution!(@run input, guard, $parser, $( [$func, $part] )*);
+        }
+
+        /// Registers the parts of this day for in-process execution.
+        #[allow(dead_code)]
+        pub fn __solution() -> $crate::template::registry::Solution {
+            $crate::template::registry::Solution {
+                day: PUZZLE,
+                parts: vec![$(
+                    ($part, $crate::template::registry::erase(|input: &str| {
+                        $crate::solution!(@call $parser, $func, input)
+                    }))
+                ),*],
+                run: __run,
+            }
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn example_cases() {
+            $crate::template::examples::run(&__solution());
+        }
+    };
+
+    (@run $input:ident, $guard:ident, [], $( [$func:expr, $part:expr] )*) => {
+        $( $guard(PUZZLE, $part, &mut || run_part($func, $input, PUZZLE, $part)); )*
+    };
+    (@run $input:ident, $guard:ident, [$kind:ident $parse:expr], $( [$func:expr, $part:expr] )*) => {
+        let mut parsed = None;
+        $guard(PUZZLE, PARSE_PART, &mut || {
+            parsed = run_parse(|input| $crate::solution!(@parse $kind $parse, input), $input, PUZZLE);
+        });
+        if let Some(parsed) = &parsed {
+            $( $guard(PUZZLE, $part, &mut || run_part(|parsed| $func(parsed), parsed, PUZZLE, $part)); )*
+        }
+    };
+
+    (@call [], $func:expr, $input:expr) => {
+        $crate::template::runner::PartResult::into_outcome($func($input))
+    };
+    (@call [$kind:ident $parse:expr], $func:expr, $input:expr) => {
+        match $crate::solution!(@parse $kind $parse, $input) {
+            Ok(parsed) => $crate::template::runner::PartResult::into_outcome($func(&parsed)),
+            Err(e) => $crate::template::runner::PartOutcome::Failed(e.to_string()),
         }
     };
+
+    (@parse parse $parse:expr, $input:expr) => {
+        Ok::<_, std::convert::Infallible>($parse($input))
+    };
+    (@parse try_parse $parse:expr, $input:expr) => {
+        $parse($input)
+    };
 }
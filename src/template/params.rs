@@ -0,0 +1,181 @@
+/// Input-dependent puzzle parameters, e.g. the size of a grid that differs between example and real input.
+///
+/// Declare the parameters of a day with [`params!`](crate::params) and pass the struct to
+/// [`solution!`](crate::solution), which generates a `params()` accessor for the part functions.
+/// Example defaults apply to `--example` runs and tests, real defaults to everything else.
+/// Single values can be overridden with `--param key=value`.
+use std::{env, fmt::Display, process, str::FromStr};
+
+use crate::template::input::Input;
+
+pub trait PuzzleParams: Sized {
+    /// Defaults for the example input.
+    fn example() -> Self;
+    /// Defaults for the real puzzle input.
+    fn real() -> Self;
+    /// Override a single parameter from its string representation.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+
+    /// Defaults that match the selected input.
+    #[must_use]
+    fn for_input(input: &Input) -> Self {
+        match input {
+            Input::Example(_) => Self::example(),
+            Input::Puzzle | Input::File(_) | Input::Stdin => Self::real(),
+        }
+    }
+}
+
+/// Parse the value of a parameter, naming the parameter on failure.
+pub fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T, String>
+where
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|e| format!("invalid value `{value}` for parameter `{key}`: {e}"))
+}
+
+/// Extract all `--param key=value` overrides from command-line arguments.
+pub fn parse_overrides(args: &[String]) -> Result<Vec<(String, String)>, String> {
+    args.iter()
+        .enumerate()
+        .filter(|(_, x)| *x == "--param")
+        .map(|(i, _)| {
+            let pair = args.get(i + 1).ok_or("`--param` expects `key=value`.")?;
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("`--param` expects `key=value`, got `{pair}`."))?;
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Resolve the parameters of a run: defaults for the given input, followed by the overrides.
+pub fn resolve<P: PuzzleParams>(args: &[String], input: &Input) -> Result<P, String> {
+    let mut params = P::for_input(input);
+
+    for (key, value) in parse_overrides(args)? {
+        params.set(&key, &value)?;
+    }
+
+    Ok(params)
+}
+
+/// Resolve the parameters from the arguments of the current process, exiting on invalid arguments.
+#[must_use]
+pub fn from_args<P: PuzzleParams>(input: &Input) -> P {
+    let args: Vec<String> = env::args().collect();
+
+    resolve(&args, input).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
+
+/// Declares a parameter struct and implements [`PuzzleParams`] for it.
+///
+/// Every field lists its example default first and its real default second. Field types need to implement `FromStr`.
+///
+/// ```ignore
+/// advent_of_code::params! {
+///     struct Params {
+///         bound: usize = (7, 71),
+///         bytes: usize = (12, 1024),
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $(#[$field_meta:meta])* $field:ident : $ty:ty = ($example:expr, $real:expr) ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq)]
+        $vis struct $name {
+            $( $(#[$field_meta])* pub $field: $ty ),*
+        }
+
+        impl $crate::template::params::PuzzleParams for $name {
+            fn example() -> Self {
+                Self { $( $field: $example ),* }
+            }
+
+            fn real() -> Self {
+                Self { $( $field: $real ),* }
+            }
+
+            fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $( stringify!($field) => {
+                        self.$field = $crate::template::params::parse_value(key, value)?;
+                    } )*
+                    _ => return Err(format!("unknown parameter `{key}`.")),
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_overrides, resolve, PuzzleParams};
+    use crate::template::input::Input;
+
+    crate::params! {
+        struct Params {
+            bound: usize = (7, 71),
+            min_saving: u32 = (1, 100),
+        }
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn selects_defaults_by_input() {
+        assert_eq!(Params::for_input(&Input::Example(None)).bound, 7);
+        assert_eq!(Params::for_input(&Input::Example(Some(2))).bound, 7);
+        assert_eq!(Params::for_input(&Input::Puzzle).bound, 71);
+        assert_eq!(Params::for_input(&Input::Stdin).min_saving, 100);
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let params: Params = resolve(
+            &args(&[
+                "18",
+                "--param",
+                "bound=11",
+                "--example",
+                "--param",
+                "min_saving = 50",
+            ]),
+            &Input::Example(None),
+        )
+        .unwrap();
+        assert_eq!(
+            params,
+            Params {
+                bound: 11,
+                min_saving: 50
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_overrides() {
+        assert!(parse_overrides(&args(&["18", "--param"])).is_err());
+        assert!(parse_overrides(&args(&["18", "--param", "bound"])).is_err());
+        assert!(resolve::<Params>(&args(&["--param", "size=1"]), &Input::Puzzle).is_err());
+        assert!(resolve::<Params>(&args(&["--param", "bound=x"]), &Input::Puzzle).is_err());
+    }
+}
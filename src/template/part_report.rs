@@ -0,0 +1,272 @@
+/// Machine-readable result of a single solution part.
+///
+/// When the environment variable [`REPORT_ENV`] holds a file path, the runner appends one JSON
+/// line per executed part to that file. `run_multi` uses this side channel instead of scraping the
+/// human-readable output, which can contain arbitrary answers (grids, parentheses, ...).
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    timings::{MemoryStats, PartStats},
+    PuzzleId,
+};
+
+/// Name of the environment variable that holds the path of the report file.
+pub const REPORT_ENV: &str = "AOC_PART_REPORT";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    /// The part returned `None`, i.e. it is not implemented yet.
+    Unsolved,
+    /// The part returned an error.
+    Failed,
+    Panicked,
+}
+
+impl PartStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Failed => "failed",
+            PartStatus::Panicked => "panicked",
+        }
+    }
+}
+
+impl FromStr for PartStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(PartStatus::Solved),
+            "unsolved" => Ok(PartStatus::Unsolved),
+            "failed" => Ok(PartStatus::Failed),
+            "panicked" => Ok(PartStatus::Panicked),
+            x => Err(format!("unknown part status `{x}`.")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: PuzzleId,
+    pub part: u8,
+    pub status: PartStatus,
+    pub answer: Option<String>,
+    /// Error message of a failed part.
+    pub error: Option<String>,
+    pub stats: PartStats,
+    /// Heap usage, only measured when the solution is compiled with the `dhat-heap` feature.
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartReport {
+    /// Append the report as a JSON line to the file named by [`REPORT_ENV`], if set.
+    pub fn emit(&self) -> Result<(), io::Error> {
+        let Some(path) = std::env::var_os(REPORT_ENV) else {
+            return Ok(());
+        };
+
+        let line = JsonValue::from(self)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{line}")
+    }
+
+    /// Read all reports from a report file. A missing file yields no reports.
+    pub fn read_all(path: &Path) -> Result<Vec<Self>, String> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.to_string()),
+        };
+
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let json = JsonValue::from_str(line).or(Err("report line is not valid JSON."))?;
+                PartReport::try_from(&json)
+            })
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("stats".into(), JsonValue::from(value.stats));
+        map.insert(
+            "memory".into(),
+            value.memory.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| PuzzleId::from_str(day).ok())
+            .ok_or("Expected report.day to be a puzzle id.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected report.part to be a number.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let error = json
+            .get("error")
+            .filter(|v| !v.is_null())
+            .map(|v| {
+                v.get::<String>()
+                    .ok_or("Expected report.error to be null or string.")
+            })
+            .transpose()?;
+
+        let stats = PartStats::try_from(
+            json.get("stats")
+                .ok_or("Expected report to have key `stats`.")?,
+        )?;
+
+        let memory = match json.get("memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(PartReport {
+            day,
+            part,
+            status,
+            answer: answer.cloned(),
+            error: error.cloned(),
+            stats,
+            memory,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartReport, PartStatus};
+    use crate::{
+        puzzle,
+        template::timings::{MemoryStats, PartStats},
+    };
+    use std::str::FromStr;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = PartReport {
+            day: puzzle!(7),
+            part: 2,
+            status: PartStatus::Solved,
+            answer: Some("a (tricky)\nmultiline answer".into()),
+            error: None,
+            stats: PartStats {
+                samples: 10.0,
+                mean_nanos: 5.0,
+                ..Default::default()
+            },
+            memory: Some(MemoryStats {
+                peak_bytes: 1024,
+                total_allocations: 7,
+            }),
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert!(!line.contains('\n'));
+        let parsed = PartReport::try_from(&JsonValue::from_str(&line).unwrap()).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn handles_unsolved_parts() {
+        let json = r#"{ "day": "01", "part": 1, "status": "unsolved", "answer": null, "stats": { "samples": 1, "mean_nanos": 0, "min_nanos": 0, "median_nanos": 0, "p95_nanos": 0, "stddev_nanos": 0 } }"#;
+        let parsed = PartReport::try_from(&JsonValue::from_str(json).unwrap()).unwrap();
+        assert_eq!(parsed.status, PartStatus::Unsolved);
+        assert_eq!(parsed.answer, None);
+        assert_eq!(parsed.error, None);
+    }
+
+    #[test]
+    fn roundtrips_failed_reports() {
+        let report = PartReport {
+            day: puzzle!(5),
+            part: 1,
+            status: PartStatus::Failed,
+            answer: None,
+            error: Some("line 3, column 1: expected integer, found `x`".into()),
+            stats: PartStats::default(),
+            memory: None,
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        let parsed = PartReport::try_from(&JsonValue::from_str(&line).unwrap()).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_unknown_status() {
+        let json = r#"{ "day": "01", "part": 1, "status": "maybe", "answer": null, "stats": {} }"#;
+        PartReport::try_from(&JsonValue::from_str(json).unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,295 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::template::{all_days, Day};
+
+/// The year of the first Advent of Code event.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// Identifies a puzzle by its day and, optionally, the year of its event.
+///
+/// Puzzles without a year belong to the repository's main event, whose year is set with `AOC_YEAR`.
+/// Their files live directly in `src/bin` and `data/<folder>`, like `src/bin/05.rs`. Puzzles of
+/// other years are stored side by side in year-qualified paths, like `src/bin/2023_05.rs` and
+/// `data/inputs/2023/05.txt`.
+///
+/// # Display
+/// This value displays as a two digit day, prefixed by the year if set.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId};
+/// let id = PuzzleId::new(Some(2023), Day::new(5).unwrap());
+/// assert_eq!(id.to_string(), "2023/05");
+/// assert_eq!("2023/05".parse::<PuzzleId>().unwrap(), id);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Option<u16>,
+    pub day: Day,
+}
+
+/// The year of the repository's main event, taken from `AOC_YEAR` at build time.
+pub const MAIN_YEAR: Option<u16> = parse_main_year(option_env!("AOC_YEAR"));
+
+const fn parse_main_year(s: Option<&str>) -> Option<u16> {
+    let Some(s) = s else {
+        return None;
+    };
+
+    let bytes = s.as_bytes();
+    if bytes.len() != 4 {
+        return None;
+    }
+
+    let mut year = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+        year = year * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+
+    Some(year)
+}
+
+/// Drop the year if it is the year of the main event.
+/// Otherwise, `2024/05` and `05` would refer to different files for `AOC_YEAR=2024`.
+const fn without_main_year(year: Option<u16>, main_year: Option<u16>) -> Option<u16> {
+    match (year, main_year) {
+        (Some(year), Some(main_year)) if year == main_year => None,
+        _ => year,
+    }
+}
+
+impl PuzzleId {
+    /// Create a puzzle id. Puzzles of the [`MAIN_YEAR`] are stored without their year.
+    pub const fn new(year: Option<u16>, day: Day) -> Self {
+        Self {
+            year: without_main_year(year, MAIN_YEAR),
+            day,
+        }
+    }
+
+    /// Name of the solution binary, e.g. `05` or `2023_05`.
+    pub fn bin_name(&self) -> String {
+        match self.year {
+            Some(year) => format!("{year}_{}", self.day),
+            None => self.day.to_string(),
+        }
+    }
+
+    /// Path of the solution binary, e.g. `./src/bin/2023_05.rs`.
+    pub fn bin_path(&self) -> String {
+        format!("./src/bin/{}.rs", self.bin_name())
+    }
+
+    /// Human readable name of the puzzle, e.g. `Day 5` or `2023 Day 5`.
+    pub fn title(&self) -> String {
+        match self.year {
+            Some(year) => format!("{year} Day {}", self.day.into_inner()),
+            None => format!("Day {}", self.day.into_inner()),
+        }
+    }
+
+    /// Parse the name of a solution binary, see [`PuzzleId::bin_name`].
+    pub fn from_bin_name(name: &str) -> Option<Self> {
+        let (year, day) = match name.split_once('_') {
+            Some((year, day)) => (Some(parse_year(year)?), day),
+            None => (None, name),
+        };
+
+        let is_day = day.len() == 2 && day.chars().all(|c| c.is_ascii_digit());
+        Some(Self::new(year, is_day.then(|| day.parse().ok())??))
+    }
+
+    /// Directory that holds the files of this puzzle in `data/<folder>`, e.g. `data/inputs/2023`.
+    pub fn data_dir(&self, folder: &str) -> PathBuf {
+        let dir = Path::new("data").join(folder);
+        match self.year {
+            Some(year) => dir.join(year.to_string()),
+            None => dir,
+        }
+    }
+
+    /// Path of a file of this puzzle in `data/<folder>`, e.g. `data/inputs/2023/05.txt`.
+    pub fn data_path(&self, folder: &str, extension: &str) -> PathBuf {
+        self.data_dir(folder)
+            .join(format!("{}.{extension}", self.day))
+    }
+}
+
+impl From<Day> for PuzzleId {
+    fn from(day: Day) -> Self {
+        Self::new(None, day)
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{year}/{}", self.day),
+            None => write!(f, "{}", self.day),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse_year(s: &str) -> Option<u16> {
+    s.parse().ok().filter(|year| *year >= FIRST_YEAR)
+}
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = match s.split_once('/') {
+            Some((year, day)) => (Some(parse_year(year).ok_or(PuzzleIdFromStrError)?), day),
+            None => (None, s),
+        };
+
+        let day = day.parse().map_err(|_| PuzzleIdFromStrError)?;
+        Ok(Self::new(year, day))
+    }
+}
+
+/// An error which can be returned when parsing a [`PuzzleId`].
+#[derive(Debug)]
+pub struct PuzzleIdFromStrError;
+
+impl Error for PuzzleIdFromStrError {}
+
+impl Display for PuzzleIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "expecting a day number between 1 and 25, optionally prefixed by a year like `2023/05`",
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Every puzzle of the main event, followed by the scaffolded puzzles of other years, sorted.
+pub fn all_puzzles() -> Vec<PuzzleId> {
+    let mut puzzles: Vec<PuzzleId> = all_days().map(PuzzleId::from).collect();
+
+    if let Ok(entries) = fs::read_dir(Path::new("src").join("bin")) {
+        let mut other_years: Vec<PuzzleId> = entries
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name().into_string().ok()?;
+                PuzzleId::from_bin_name(name.strip_suffix(".rs")?)
+            })
+            .filter(|id| id.year.is_some())
+            .collect();
+
+        other_years.sort_unstable();
+        puzzles.extend(other_years);
+    }
+
+    puzzles
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`PuzzleId`] value in a const context, e.g. `puzzle!(5)` or `puzzle!(2023 / 5)`.
+#[macro_export]
+macro_rules! puzzle {
+    ($year:literal / $day:literal) => {{
+        const _ASSERT: () = assert!(
+            $year >= $crate::template::FIRST_YEAR,
+            concat!("invalid year `", $year, "`, Advent of Code started in 2015")
+        );
+        $crate::template::PuzzleId::new(Some($year), $crate::day!($day))
+    }};
+    ($day:expr) => {
+        $crate::template::PuzzleId::new(None, $crate::day!($day))
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{parse_main_year, without_main_year, PuzzleId, MAIN_YEAR};
+    use crate::day;
+
+    #[test]
+    fn parses_puzzle_ids() {
+        assert_eq!(
+            "5".parse::<PuzzleId>().unwrap(),
+            PuzzleId::new(None, day!(5))
+        );
+        assert_eq!(
+            "2023/05".parse::<PuzzleId>().unwrap(),
+            PuzzleId::new(Some(2023), day!(5))
+        );
+        assert!("2014/05".parse::<PuzzleId>().is_err());
+        assert!("2023/26".parse::<PuzzleId>().is_err());
+        assert!("2023-05".parse::<PuzzleId>().is_err());
+    }
+
+    #[test]
+    fn normalizes_year_of_main_event() {
+        assert_eq!(without_main_year(Some(2024), Some(2024)), None);
+        assert_eq!(without_main_year(Some(2023), Some(2024)), Some(2023));
+        assert_eq!(without_main_year(Some(2024), None), Some(2024));
+        assert_eq!(parse_main_year(Some("2024")), Some(2024));
+        assert_eq!(parse_main_year(Some("24")), None);
+        assert_eq!(parse_main_year(None), None);
+    }
+
+    #[test]
+    fn addresses_main_event_consistently() {
+        // `AOC_YEAR` is set to 2024 in `.cargo/config.toml`.
+        assert_eq!(MAIN_YEAR, Some(2024));
+
+        let id = PuzzleId::new(None, day!(5));
+        assert_eq!(puzzle!(2024 / 5), id);
+        assert_eq!("2024/05".parse::<PuzzleId>().unwrap(), id);
+        assert_eq!(PuzzleId::from_bin_name("2024_05"), Some(id));
+        assert_eq!(PuzzleId::new(Some(2024), day!(5)).bin_name(), "05");
+    }
+
+    #[test]
+    fn displays_puzzle_ids() {
+        assert_eq!(PuzzleId::new(None, day!(5)).to_string(), "05");
+        assert_eq!(PuzzleId::new(Some(2015), day!(25)).to_string(), "2015/25");
+        assert_eq!(PuzzleId::new(None, day!(5)).title(), "Day 5");
+        assert_eq!(PuzzleId::new(Some(2023), day!(5)).title(), "2023 Day 5");
+    }
+
+    #[test]
+    fn handles_bin_names() {
+        let id = PuzzleId::new(Some(2023), day!(5));
+
+        assert_eq!(id.bin_name(), "2023_05");
+        assert_eq!(id.bin_path(), "./src/bin/2023_05.rs");
+        assert_eq!(PuzzleId::from_bin_name("2023_05"), Some(id));
+        assert_eq!(
+            PuzzleId::from_bin_name("05"),
+            Some(PuzzleId::new(None, day!(5)))
+        );
+        assert_eq!(PuzzleId::from_bin_name("5"), None);
+        assert_eq!(PuzzleId::from_bin_name("2023_5"), None);
+        assert_eq!(PuzzleId::from_bin_name("main"), None);
+    }
+
+    #[test]
+    fn handles_data_paths() {
+        assert_eq!(
+            PuzzleId::new(None, day!(5)).data_path("inputs", "txt"),
+            Path::new("data/inputs/05.txt")
+        );
+        assert_eq!(
+            PuzzleId::new(Some(2023), day!(5)).data_path("puzzles", "md"),
+            Path::new("data/puzzles/2023/05.md")
+        );
+    }
+}
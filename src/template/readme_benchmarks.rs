@@ -2,11 +2,12 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{report, timings::Timings};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+pub static README_PATH: &str = "README.md";
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -25,11 +26,6 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -52,26 +48,41 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+pub fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    // memory columns are only shown once a day has been benched with `--memory`.
+    let has_memory = report::has_memory(&timings);
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_memory {
+        lines.push(
+            "| Day | Parse | Part 1 | Part 2 | Parse Memory | Part 1 Memory | Part 2 Memory |"
+                .into(),
+        );
+        lines.push("| :---: | :---: | :---: | :---: | :---: | :---: | :---:  |".into());
+    } else {
+        lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---: | :---:  |".into());
+    }
+
+    for timing in &timings.data {
+        let phases = report::phases(timing);
+        let mut line = format!("| [{}]({}) |", timing.day.title(), timing.day.bin_path());
+
+        for phase in &phases {
+            line.push_str(&format!(" `{}` |", phase.time));
+        }
+
+        if has_memory {
+            for phase in &phases {
+                let memory = phase.memory.map_or_else(|| "-".into(), |m| m.to_string());
+                line.push_str(&format!(" `{memory}` |"));
+            }
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
@@ -89,43 +100,17 @@ fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let mut readme = String::from_utf8_lossy(&fs::read(README_PATH)?).to_string();
     let total_millis = timings.total_millis();
     update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+    fs::write(README_PATH, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
-
-    fn get_mock_timings() -> Timings {
-        Timings {
-            data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
-                },
-            ],
-        }
-    }
+    use crate::template::timings::{fixtures::get_mock_timings, DayStatus, MemoryStats};
 
     #[test]
     #[should_panic]
@@ -167,11 +152,11 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +165,40 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn formats_failed_days() {
+        let mut timings = get_mock_timings();
+        timings.data[1].part_2 = None;
+        timings.data[1].status = DayStatus::Timeout;
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `TIMEOUT` |"));
+    }
+
+    #[test]
+    fn formats_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_memory = Some(MemoryStats {
+            peak_bytes: 512,
+            total_allocations: 3,
+        });
+        timings.data[0].part_2_memory = Some(MemoryStats {
+            peak_bytes: 3 * 1024 * 1024 / 2,
+            total_allocations: 1200,
+        });
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains(
+            "| Day | Parse | Part 1 | Part 2 | Parse Memory | Part 1 Memory | Part 2 Memory |"
+        ));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` | `-` | `512 B / 3 allocs` | `1.5 MiB / 1200 allocs` |"
+        ));
+        assert!(
+            s.contains("| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` | `-` | `-` | `-` |")
+        );
+    }
 }
@@ -0,0 +1,108 @@
+/// In-process execution of all solutions.
+///
+/// Besides its `main`, the [`solution!`](crate::solution) macro generates a `__solution()` function
+/// that returns the day's parts as type-erased closures. The build script compiles every
+/// scaffolded `src/bin/NN.rs` as a module of the `aoc_all` binary, which runs them one after
+/// another via [`run`], so `cargo all` and `cargo time` only pay cargo's start-up cost once.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::template::{
+    part_report::{PartReport, PartStatus},
+    runner::{phase_label, Guard, PartOutcome, PartResult},
+    timings::PartStats,
+    PuzzleId, ANSI_BOLD, ANSI_RESET,
+};
+
+/// A solution part whose answer type has been erased.
+pub type ErasedPart = Box<dyn Fn(&str) -> PartOutcome<Box<dyn Display>>>;
+
+/// The parts of a single day, as registered by the `solution!` macro.
+pub struct Solution {
+    pub day: PuzzleId,
+    /// The parts on their own, each including the parse phase of the day.
+    pub parts: Vec<(u8, ErasedPart)>,
+    /// Runs all phases of the day on an input, reporting the parse phase and each part separately.
+    pub run: fn(&str, Guard),
+}
+
+/// Erase the answer type of a solution part.
+pub fn erase<R: PartResult>(func: impl Fn(&str) -> R + 'static) -> ErasedPart
+where
+    R::Answer: 'static,
+{
+    Box::new(move |input| {
+        func(input)
+            .into_outcome()
+            .map(|x| Box::new(x) as Box<dyn Display>)
+    })
+}
+
+/// Print the header that precedes the output of a day.
+pub fn print_day_header(day: PuzzleId, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Entry point of the aggregate binary.
+/// Runs the registered solutions for the days passed as arguments, or all of them if none are passed.
+pub fn run(mut solutions: Vec<Solution>) {
+    solutions.sort_unstable_by_key(|s| s.day);
+
+    let mut days: Vec<PuzzleId> = env::args().skip(1).filter_map(|x| x.parse().ok()).collect();
+
+    if days.is_empty() {
+        days = solutions.iter().map(|s| s.day).collect();
+    }
+
+    for (i, day) in days.iter().enumerate() {
+        print_day_header(*day, i > 0);
+
+        let Some(solution) = solutions.iter().find(|s| s.day == *day) else {
+            println!("Not solved.");
+            continue;
+        };
+
+        let path = env::current_dir()
+            .unwrap()
+            .join(day.data_path("inputs", "txt"));
+
+        let Ok(input) = fs::read_to_string(&path) else {
+            eprintln!("could not open input file {}", path.display());
+            continue;
+        };
+
+        (solution.run)(&input, guarded);
+    }
+}
+
+/// Runs a phase of a solution, reporting it as panicked instead of aborting the run if it panics.
+pub fn guarded(day: PuzzleId, part: u8, run: &mut dyn FnMut()) {
+    if panic::catch_unwind(AssertUnwindSafe(run)).is_ok() {
+        return;
+    }
+
+    println!("\r{}: panicked.", phase_label(part));
+
+    let report = PartReport {
+        day,
+        part,
+        status: PartStatus::Panicked,
+        answer: None,
+        error: None,
+        stats: PartStats::default(),
+        memory: None,
+    };
+
+    if let Err(e) = report.emit() {
+        eprintln!("Failed to write part report: {e}");
+    }
+}
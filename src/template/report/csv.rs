@@ -0,0 +1,66 @@
+/// Renders timings as CSV, with one row per day.
+///
+/// Times are raw mean nanoseconds and memory is raw peak bytes, so the file can be loaded
+/// into a spreadsheet without parsing units. Phases without a measurement are left empty.
+use crate::template::timings::Timings;
+
+use super::{phases, Renderer};
+
+pub struct Csv;
+
+static HEADER: &str = "day,status,parse_nanos,part_1_nanos,part_2_nanos,total_nanos,\
+parse_peak_bytes,part_1_peak_bytes,part_2_peak_bytes,\
+parse_allocations,part_1_allocations,part_2_allocations";
+
+impl Renderer for Csv {
+    fn path(&self) -> &'static str {
+        "./data/benchmarks.csv"
+    }
+
+    fn render(&self, timings: &Timings) -> String {
+        let mut lines = vec![HEADER.to_string()];
+
+        for timing in &timings.data {
+            let phases = phases(timing);
+
+            let mut row = vec![timing.day.to_string(), timing.status.as_str().into()];
+            row.extend(phases.iter().map(|p| {
+                p.stats
+                    .map_or_else(String::new, |s| s.mean_nanos.to_string())
+            }));
+            row.push(timing.total_nanos.to_string());
+            row.extend(phases.iter().map(|p| {
+                p.memory
+                    .map_or_else(String::new, |m| m.peak_bytes.to_string())
+            }));
+            row.extend(phases.iter().map(|p| {
+                p.memory
+                    .map_or_else(String::new, |m| m.total_allocations.to_string())
+            }));
+
+            lines.push(row.join(","));
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Csv, HEADER};
+    use crate::template::report::{tests::get_mock_timings, Renderer};
+
+    #[test]
+    fn renders_rows() {
+        let csv = Csv.render(&get_mock_timings());
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], HEADER);
+        assert_eq!(lines[1], "01,ok,120,10000,2500000,2510120,,512,,,3,");
+        assert_eq!(lines[2], "02,panic,,4,,4,,,,,,");
+        assert_eq!(lines.len(), 3);
+    }
+}
@@ -0,0 +1,108 @@
+/// Renders timings as a standalone HTML page with the benchmark table and the SVG chart.
+use crate::template::{
+    runner::{phase_label, PARSE_PART},
+    timings::Timings,
+};
+
+use super::{escape_xml, has_memory, phases, svg::render_chart, Renderer};
+
+pub struct Html;
+
+static STYLE: &str = "body { font-family: sans-serif; margin: 2rem; }
+table { border-collapse: collapse; margin-bottom: 1rem; }
+th, td { border: 1px solid #ddd; padding: 0.25rem 0.75rem; text-align: center; }
+th { background: #f5f5f5; }";
+
+impl Renderer for Html {
+    fn path(&self) -> &'static str {
+        "./data/benchmarks.html"
+    }
+
+    fn render(&self, timings: &Timings) -> String {
+        let has_memory = has_memory(timings);
+
+        let labels = [PARSE_PART, 1, 2].map(phase_label);
+        let mut header = vec!["Day".to_string()];
+        header.extend(labels.iter().cloned());
+        if has_memory {
+            header.extend(labels.iter().map(|label| format!("{label} Memory")));
+        }
+
+        let mut rows = vec![format!(
+            "<tr>{}</tr>",
+            header
+                .iter()
+                .map(|h| format!("<th>{}</th>", escape_xml(h)))
+                .collect::<String>()
+        )];
+
+        for timing in &timings.data {
+            let phases = phases(timing);
+            let mut cells = vec![timing.day.title()];
+            cells.extend(phases.iter().map(|p| p.time.clone()));
+            if has_memory {
+                cells.extend(
+                    phases
+                        .iter()
+                        .map(|p| p.memory.map_or_else(|| "-".into(), |m| m.to_string())),
+                );
+            }
+
+            rows.push(format!(
+                "<tr>{}</tr>",
+                cells
+                    .iter()
+                    .map(|c| format!("<td>{}</td>", escape_xml(c)))
+                    .collect::<String>()
+            ));
+        }
+
+        format!(
+            "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Benchmarks</title>
+<style>
+{STYLE}
+</style>
+</head>
+<body>
+<h1>Benchmarks</h1>
+<table>
+{}
+</table>
+<p><strong>Total: {:.2}ms</strong></p>
+{}</body>
+</html>
+",
+            rows.join("\n"),
+            timings.total_millis(),
+            render_chart(timings)
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Html;
+    use crate::template::report::{tests::get_mock_timings, Renderer};
+
+    #[test]
+    fn renders_page() {
+        let html = Html.render(&get_mock_timings());
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(
+            "<tr><th>Day</th><th>Parse</th><th>Part 1</th><th>Part 2</th><th>Parse Memory</th><th>Part 1 Memory</th><th>Part 2 Memory</th></tr>"
+        ));
+        assert!(html.contains(
+            "<tr><td>Day 1</td><td>120.0ns</td><td>10.0µs</td><td>2.5ms</td><td>-</td><td>512 B / 3 allocs</td><td>-</td></tr>"
+        ));
+        assert!(html.contains("<td>Day 2</td><td>-</td><td>4.0ns</td><td>PANIC</td>"));
+        assert!(html.contains("<strong>Total: 2.51ms</strong>"));
+        assert!(html.contains("<svg"));
+    }
+}
@@ -0,0 +1,218 @@
+/// Module that renders stored timings into reports.
+///
+/// Every output format implements [`Renderer`]. The readme table is spliced into `README.md`,
+/// all other formats are written as standalone files to `data/`.
+use std::{fmt::Display, fs, str::FromStr};
+
+use crate::template::{
+    readme_benchmarks::{self, Error},
+    runner::{phase_label, PARSE_PART},
+    timings::{DayStatus, MemoryStats, PartStats, Timing, Timings},
+};
+
+mod csv;
+mod html;
+mod svg;
+
+/// A report output format, selected with `cargo time --store --format <format>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Readme,
+    Html,
+    Csv,
+    Svg,
+}
+
+impl Format {
+    fn renderer(self) -> &'static dyn Renderer {
+        match self {
+            Format::Readme => &Readme,
+            Format::Html => &html::Html,
+            Format::Csv => &csv::Csv,
+            Format::Svg => &svg::Svg,
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "readme" => Ok(Format::Readme),
+            "html" => Ok(Format::Html),
+            "csv" => Ok(Format::Csv),
+            "svg" => Ok(Format::Svg),
+            _ => Err(format!(
+                "unknown format `{s}`, expected one of: readme, html, csv, svg"
+            )),
+        }
+    }
+}
+
+/// Renders timings into one output format.
+pub trait Renderer {
+    /// Path the report is written to.
+    fn path(&self) -> &'static str;
+
+    /// Render the complete report.
+    fn render(&self, timings: &Timings) -> String;
+
+    /// Write the report to [`Renderer::path`].
+    fn write(&self, timings: &Timings) -> Result<(), Error> {
+        fs::write(self.path(), self.render(timings))?;
+        Ok(())
+    }
+}
+
+/// The benchmark table in `README.md`, see [`readme_benchmarks`].
+struct Readme;
+
+impl Renderer for Readme {
+    fn path(&self) -> &'static str {
+        readme_benchmarks::README_PATH
+    }
+
+    fn render(&self, timings: &Timings) -> String {
+        readme_benchmarks::construct_table("##", timings.clone(), timings.total_millis())
+    }
+
+    fn write(&self, timings: &Timings) -> Result<(), Error> {
+        readme_benchmarks::update(timings.clone())
+    }
+}
+
+/// Write `timings` in every format, returning the paths that were written.
+pub fn write(timings: &Timings, formats: &[Format]) -> Result<Vec<&'static str>, Error> {
+    formats
+        .iter()
+        .map(|format| {
+            let renderer = format.renderer();
+            renderer.write(timings).map(|()| renderer.path())
+        })
+        .collect()
+}
+
+/// A single phase of a day, as shown in a report.
+pub(crate) struct Phase {
+    pub(crate) label: String,
+    /// Formatted mean, or why the phase has no timing.
+    pub(crate) time: String,
+    pub(crate) stats: Option<PartStats>,
+    pub(crate) memory: Option<MemoryStats>,
+}
+
+/// The parse phase and both parts of a timing.
+pub(crate) fn phases(timing: &Timing) -> [Phase; 3] {
+    // parts without a timing show why the day did not finish.
+    let placeholder = match timing.status {
+        DayStatus::Failed | DayStatus::Panic | DayStatus::Timeout => timing.status.to_string(),
+        DayStatus::Ok | DayStatus::NotSolved => "-".into(),
+    };
+
+    let phase = |part, time: &Option<String>, stats, memory, placeholder: &str| Phase {
+        label: phase_label(part),
+        time: time.clone().unwrap_or_else(|| placeholder.into()),
+        stats,
+        memory,
+    };
+
+    [
+        phase(
+            PARSE_PART,
+            &timing.parse,
+            timing.parse_stats,
+            timing.parse_memory,
+            "-",
+        ),
+        phase(
+            1,
+            &timing.part_1,
+            timing.part_1_stats,
+            timing.part_1_memory,
+            &placeholder,
+        ),
+        phase(
+            2,
+            &timing.part_2,
+            timing.part_2_stats,
+            timing.part_2_memory,
+            &placeholder,
+        ),
+    ]
+}
+
+/// Whether any timing was benched with `--memory`.
+pub(crate) fn has_memory(timings: &Timings) -> bool {
+    timings
+        .data
+        .iter()
+        .any(|t| phases(t).iter().any(|p| p.memory.is_some()))
+}
+
+/// Escape text for use in HTML and SVG documents.
+fn escape_xml(s: impl Display) -> String {
+    s.to_string()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Format;
+    use crate::{
+        puzzle,
+        template::timings::{DayStatus, MemoryStats, PartStats, Timing, Timings},
+    };
+
+    pub(super) fn get_mock_timings() -> Timings {
+        let stats = |mean_nanos| {
+            Some(PartStats {
+                samples: 100_f64,
+                mean_nanos,
+                ..Default::default()
+            })
+        };
+
+        Timings {
+            data: vec![
+                Timing {
+                    day: puzzle!(1),
+                    part_1: Some("10.0µs".into()),
+                    part_2: Some("2.5ms".into()),
+                    part_1_stats: stats(10_000_f64),
+                    part_2_stats: stats(2_500_000_f64),
+                    parse: Some("120.0ns".into()),
+                    parse_stats: stats(120_f64),
+                    part_1_memory: Some(MemoryStats {
+                        peak_bytes: 512,
+                        total_allocations: 3,
+                    }),
+                    total_nanos: 2_510_120_f64,
+                    ..Default::default()
+                },
+                Timing {
+                    day: puzzle!(2),
+                    part_1: Some("4.0ns".into()),
+                    part_1_stats: stats(4_f64),
+                    status: DayStatus::Panic,
+                    total_nanos: 4_f64,
+                    ..Default::default()
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("readme".parse(), Ok(Format::Readme));
+        assert_eq!("html".parse(), Ok(Format::Html));
+        assert_eq!("csv".parse(), Ok(Format::Csv));
+        assert_eq!("svg".parse(), Ok(Format::Svg));
+        assert!("pdf".parse::<Format>().is_err());
+    }
+}
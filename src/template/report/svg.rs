@@ -0,0 +1,172 @@
+/// Renders timings as an SVG bar chart with one group of bars per day.
+///
+/// Runtimes span several orders of magnitude, so the time axis is logarithmic with a grid line
+/// per decade. Phases without stats have no bar.
+use crate::template::{
+    runner::{phase_label, PARSE_PART},
+    timings::Timings,
+};
+
+use super::{escape_xml, phases, Renderer};
+
+pub struct Svg;
+
+const WIDTH: f64 = 800_f64;
+const MARGIN_LEFT: f64 = 70_f64;
+const MARGIN_RIGHT: f64 = 30_f64;
+const MARGIN_TOP: f64 = 40_f64;
+const MARGIN_BOTTOM: f64 = 30_f64;
+const BAR_HEIGHT: f64 = 8_f64;
+const ROW_HEIGHT: f64 = 3_f64 * BAR_HEIGHT + 10_f64;
+
+/// Fill colors of the parse phase, part one and part two.
+const COLORS: [&str; 3] = ["#9e9e9e", "#4e79a7", "#f28e2b"];
+
+impl Renderer for Svg {
+    fn path(&self) -> &'static str {
+        "./data/benchmarks.svg"
+    }
+
+    fn render(&self, timings: &Timings) -> String {
+        render_chart(timings)
+    }
+}
+
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+pub(super) fn render_chart(timings: &Timings) -> String {
+    let means: Vec<f64> = timings
+        .data
+        .iter()
+        .flat_map(phases)
+        .filter_map(|p| p.stats.map(|s| s.mean_nanos.max(1_f64)))
+        .collect();
+
+    // the axis spans full decades around all measured means.
+    let min_decade = means.iter().fold(f64::MAX, |a, b| a.min(b.log10().floor()));
+    let max_decade = means.iter().fold(f64::MIN, |a, b| a.max(b.log10().ceil()));
+    let (min_decade, max_decade) = if means.is_empty() {
+        (0_f64, 1_f64)
+    } else {
+        (min_decade, max_decade.max(min_decade + 1_f64))
+    };
+
+    let plot_width = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_height = timings.data.len() as f64 * ROW_HEIGHT;
+    let height = MARGIN_TOP + plot_height + MARGIN_BOTTOM;
+    let x = |nanos: f64| {
+        MARGIN_LEFT
+            + (nanos.max(1_f64).log10() - min_decade) / (max_decade - min_decade) * plot_width
+    };
+
+    let mut elements = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="sans-serif" font-size="12">"#
+        ),
+        format!(r#"<rect width="{WIDTH}" height="{height}" fill="white"/>"#),
+    ];
+
+    for (i, (part, color)) in [PARSE_PART, 1, 2].into_iter().zip(COLORS).enumerate() {
+        let label = phase_label(part);
+        let left = MARGIN_LEFT + i as f64 * 90_f64;
+        elements.push(format!(
+            r#"<rect x="{left}" y="12" width="12" height="12" fill="{color}"/><text x="{}" y="22">{label}</text>"#,
+            left + 16_f64
+        ));
+    }
+
+    for decade in min_decade as i32..=max_decade as i32 {
+        let left = x(10_f64.powi(decade));
+        let bottom = MARGIN_TOP + plot_height;
+        elements.push(format!(
+            r##"<line x1="{left:.1}" y1="{MARGIN_TOP}" x2="{left:.1}" y2="{bottom}" stroke="#dddddd"/><text x="{left:.1}" y="{:.1}" text-anchor="middle">{}</text>"##,
+            bottom + 18_f64,
+            format_decade(decade)
+        ));
+    }
+
+    for (row, timing) in timings.data.iter().enumerate() {
+        let top = MARGIN_TOP + row as f64 * ROW_HEIGHT + 5_f64;
+        elements.push(format!(
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="end">Day {}</text>"#,
+            MARGIN_LEFT - 8_f64,
+            top + 1.5_f64 * BAR_HEIGHT + 4_f64,
+            timing.day
+        ));
+
+        for (i, phase) in phases(timing).iter().enumerate() {
+            let Some(stats) = phase.stats else {
+                continue;
+            };
+
+            elements.push(format!(
+                r#"<rect x="{MARGIN_LEFT}" y="{:.1}" width="{:.1}" height="{BAR_HEIGHT}" fill="{}"><title>Day {} {}: {}</title></rect>"#,
+                top + i as f64 * BAR_HEIGHT,
+                x(stats.mean_nanos) - MARGIN_LEFT,
+                COLORS[i],
+                timing.day,
+                phase.label,
+                escape_xml(&phase.time)
+            ));
+        }
+    }
+
+    elements.push("</svg>".into());
+    elements.push(String::new());
+    elements.join("\n")
+}
+
+/// Label of the grid line at `10^decade` nanoseconds, e.g. `100µs`.
+fn format_decade(decade: i32) -> String {
+    let (unit, exponent) = match decade {
+        ..=2 => ("ns", decade),
+        3..=5 => ("µs", decade - 3),
+        6..=8 => ("ms", decade - 6),
+        _ => ("s", decade - 9),
+    };
+
+    format!("{}{unit}", 10_u64.pow(exponent.max(0).unsigned_abs()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_decade, Svg};
+    use crate::template::{
+        report::{tests::get_mock_timings, Renderer},
+        timings::Timings,
+    };
+
+    #[test]
+    fn formats_decades() {
+        assert_eq!(format_decade(0), "1ns");
+        assert_eq!(format_decade(2), "100ns");
+        assert_eq!(format_decade(4), "10µs");
+        assert_eq!(format_decade(6), "1ms");
+        assert_eq!(format_decade(10), "10s");
+    }
+
+    #[test]
+    fn renders_log_scale_bars() {
+        let svg = Svg.render(&get_mock_timings());
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+
+        // means range from 4ns to 2.5ms, so the axis spans 1ns to 10ms.
+        assert!(svg.contains(">1ns</text>"));
+        assert!(svg.contains(">10ms</text>"));
+        assert!(!svg.contains(">100ms</text>"));
+
+        assert!(svg.contains("<title>Day 01 Part 2: 2.5ms</title>"));
+        assert!(svg.contains("<title>Day 02 Part 1: 4.0ns</title>"));
+        assert!(!svg.contains("Day 02 Part 2"));
+    }
+
+    #[test]
+    fn renders_empty_timings() {
+        let svg = Svg.render(&Timings::default());
+        assert!(svg.contains(">1ns</text>"));
+        assert!(!svg.contains("<title>"));
+    }
+}
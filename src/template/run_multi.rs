@@ -1,49 +1,268 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    path::Path,
+    process::{self, ExitStatus},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
+use self::child_commands::{ChildExit, Profile};
 use super::{
-    all_days,
-    timings::{Timing, Timings},
+    all_puzzles,
+    part_report::PartReport,
+    registry::print_day_header,
+    runner::{phase_label, PARSE_PART},
+    timings::{DayStatus, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Outcome of running a single day.
+pub struct DayRun {
+    pub day: PuzzleId,
+    pub status: DayStatus,
+    pub reports: Vec<PartReport>,
+}
 
-    let mut need_space = false;
+/// Outcome of running a set of days.
+pub struct MultiRun {
+    /// Timings of every scaffolded day, only present for timed runs.
+    pub timings: Option<Timings>,
+    /// Runs of every scaffolded day, sorted by day.
+    pub days: Vec<DayRun>,
+}
 
+/// Run the given days. With `in_process`, all days are executed by the single `aoc_all` binary
+/// instead of one `cargo run` per day. Otherwise, untimed runs execute up to `jobs` days concurrently
+/// and every day is killed once it runs longer than `timeout`.
+/// With `memory`, timed runs are followed by a run under dhat that measures the heap usage of every part.
+pub fn run_multi(
+    days_to_run: &HashSet<PuzzleId>,
+    is_release: bool,
+    is_timed: bool,
+    in_process: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    memory: bool,
+) -> MultiRun {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<PuzzleId> = all_puzzles()
+        .into_iter()
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        .collect();
+    let profile = Profile::from_release(is_release);
+
+    let day_runs: Vec<DayRun> = if in_process {
+        let output = child_commands::run_all_in_process(&days, is_timed, profile).unwrap();
+
+        days.iter()
+            .filter(|day| Path::new(&day.bin_path()).exists())
+            .map(|day| {
+                let reports: Vec<PartReport> = output
+                    .reports
+                    .iter()
+                    .filter(|r| r.day == *day)
+                    .cloned()
+                    .collect();
+                // if `aoc_all` aborted, e.g. on a stack overflow, the days it did not report on have failed.
+                let exit = if reports.is_empty() {
+                    &output.exit
+                } else {
+                    &ChildExit::Success
+                };
+                DayRun {
+                    day: *day,
+                    status: child_commands::day_status(exit, &reports),
+                    reports,
+                }
+            })
+            .collect()
+    } else if jobs > 1 && !is_timed {
+        run_parallel(&days, profile, jobs, timeout)
+    } else {
+        if timeout.is_some() {
+            // build up-front, so compilation does not count towards the timeout.
+            build_bins(profile);
+        }
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        let mut day_runs = Vec::with_capacity(days.len());
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        for (i, day) in days.iter().enumerate() {
+            print_day_header(*day, i > 0);
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            match child_commands::run_solution(*day, is_timed, profile, false, timeout).unwrap() {
+                Some(output) => day_runs.push(output.into_day_run(*day)),
+                None => println!("Not solved."),
             }
-        });
+        }
+
+        day_runs
+    };
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    print_summary(&day_runs);
+
+    let timings = if is_timed {
+        let mut timings = Timings {
+            data: day_runs
+                .iter()
+                .map(|run| child_commands::timing_from_reports(&run.reports, run.day, run.status))
+                .collect::<Vec<Timing>>(),
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
+
+        if memory {
+            measure_memory(&mut timings, timeout);
+        }
+
         Some(timings)
     } else {
         None
+    };
+
+    MultiRun {
+        timings,
+        days: day_runs,
+    }
+}
+
+/// Run every timed day once more under dhat and attach the heap usage of its phases to its timing.
+/// Timing the dhat build would be meaningless, since dhat slows down every allocation.
+fn measure_memory(timings: &mut Timings, timeout: Option<Duration>) {
+    println!("\n{ANSI_BOLD}Memory{ANSI_RESET}");
+
+    build_bins(Profile::Dhat);
+
+    for timing in &mut timings.data {
+        let output =
+            match child_commands::run_solution(timing.day, false, Profile::Dhat, true, timeout) {
+                Ok(Some(output)) => output,
+                Ok(None) => continue,
+                Err(e) => {
+                    eprintln!("Failed to measure memory of day {}: {e:?}", timing.day);
+                    continue;
+                }
+            };
+
+        for report in output.reports {
+            let Some(memory) = report.memory else {
+                continue;
+            };
+
+            println!("Day {} {}: {memory}", timing.day, phase_label(report.part));
+
+            match report.part {
+                PARSE_PART => timing.parse_memory = Some(memory),
+                1 => timing.part_1_memory = Some(memory),
+                2 => timing.part_2_memory = Some(memory),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Build all binaries up-front, exiting early if the build fails.
+/// Otherwise, every child run would retry the failed build and count its compile time towards the timeout.
+fn build_bins(profile: Profile) {
+    if let Err(e) = child_commands::build_bins(profile) {
+        match e {
+            Error::Build(status) => {
+                eprintln!("Failed to build solutions: cargo build exited with {status}.")
+            }
+            e => eprintln!("Failed to build solutions: {e:?}"),
+        }
+        process::exit(1);
+    }
+}
+
+/// Print the days that did not finish successfully, if any, with the errors of their failed parts.
+fn print_summary(day_runs: &[DayRun]) {
+    let failed: Vec<&DayRun> = day_runs
+        .iter()
+        .filter(|run| run.status != DayStatus::Ok)
+        .collect();
+
+    if failed.is_empty() {
+        return;
     }
+
+    println!("\n{ANSI_BOLD}Summary{ANSI_RESET}");
+
+    for run in failed {
+        println!("Day {}: {}", run.day, run.status);
+
+        for report in &run.reports {
+            if let Some(error) = &report.error {
+                println!("  {}: {error}", phase_label(report.part));
+            }
+        }
+    }
+}
+
+/// Run days concurrently on `jobs` threads, buffering the output of each child.
+/// Outputs are printed in day order as soon as all preceding days have finished.
+fn run_parallel(
+    days: &[PuzzleId],
+    profile: Profile,
+    jobs: usize,
+    timeout: Option<Duration>,
+) -> Vec<DayRun> {
+    // build all binaries up-front, so the jobs do not block each other on cargo's build lock.
+    build_bins(profile);
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    let mut day_runs = Vec::with_capacity(days.len());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let next = &next;
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(day) = days.get(index) else {
+                    break;
+                };
+                let output = child_commands::run_solution(*day, false, profile, true, timeout);
+                sender.send((index, output)).unwrap();
+            });
+        }
+
+        drop(sender);
+
+        let mut finished = BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (index, output) in receiver {
+            finished.insert(index, output);
+
+            while let Some(output) = finished.remove(&next_to_print) {
+                let day = days[next_to_print];
+                print_day_header(day, next_to_print > 0);
+
+                match output.unwrap() {
+                    Some(output) => {
+                        output.stdout.iter().for_each(|line| println!("{line}"));
+                        output.stderr.iter().for_each(|line| eprintln!("{line}"));
+                        day_runs.push(output.into_day_run(day));
+                    }
+                    None => println!("Not solved."),
+                }
+
+                next_to_print += 1;
+            }
+        }
+    });
+
+    day_runs
 }
 
 #[allow(dead_code)]
@@ -51,6 +270,9 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    Report(String),
+    /// `cargo build` exited unsuccessfully.
+    Build(ExitStatus),
 }
 
 impl From<std::io::Error> for Error {
@@ -59,48 +281,166 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// All solutions live in isolated binaries, which are also compiled into the aggregate `aoc_all` binary.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their part reports.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{DayRun, Error};
+    use crate::template::{
+        part_report::{PartReport, PartStatus, REPORT_ENV},
+        runner::PARSE_PART,
+        timings::DayStatus,
+        PuzzleId,
+    };
     use std::{
+        env, fs,
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{self, Child, Command, ExitStatus, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Cargo profile the solution binaries are built and run with.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Profile {
+        Debug,
+        Release,
+        /// Release build with the `dhat-heap` feature, which measures the heap usage of every part.
+        Dhat,
+    }
+
+    impl Profile {
+        pub fn from_release(is_release: bool) -> Self {
+            if is_release {
+                Profile::Release
+            } else {
+                Profile::Debug
+            }
+        }
+
+        fn cargo_args(self) -> &'static [&'static str] {
+            match self {
+                Profile::Debug => &[],
+                Profile::Release => &["--release"],
+                Profile::Dhat => &["--profile", "dhat", "--features", "dhat-heap"],
+            }
+        }
+    }
+
+    /// How a child process terminated.
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum ChildExit {
+        Success,
+        /// Non-zero exit code, or `None` if the child was terminated by a signal.
+        Failure(Option<i32>),
+        TimedOut,
+    }
+
+    impl From<ExitStatus> for ChildExit {
+        fn from(status: ExitStatus) -> Self {
+            if status.success() {
+                ChildExit::Success
+            } else {
+                ChildExit::Failure(status.code())
+            }
+        }
+    }
+
+    /// Output of a solution bin.
+    pub struct ChildOutput {
+        /// Lines written to stdout, only collected when the output is buffered.
+        pub stdout: Vec<String>,
+        /// Lines written to stderr, only collected when the output is buffered.
+        pub stderr: Vec<String>,
+        pub exit: ChildExit,
+        pub reports: Vec<PartReport>,
+    }
+
+    impl ChildOutput {
+        pub fn into_day_run(self, day: PuzzleId) -> DayRun {
+            DayRun {
+                day,
+                status: day_status(&self.exit, &self.reports),
+                reports: self.reports,
+            }
+        }
+    }
+
+    /// Run the solution bin for a given day and collect the reports of its parts.
+    /// With `buffer`, the output of the child is collected instead of being forwarded.
+    /// The child is killed once it runs longer than `timeout`.
+    /// Returns `None` for days that have not been scaffolded yet.
+    pub fn run_solution(
+        day: PuzzleId,
+        is_timed: bool,
+        profile: Profile,
+        buffer: bool,
+        timeout: Option<Duration>,
+    ) -> Result<Option<ChildOutput>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&day.bin_path()).exists() {
+            return Ok(None);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        run_bin(&day.bin_name(), &[], is_timed, profile, buffer, timeout).map(Some)
+    }
 
-        if is_release {
-            args.push("--release");
+    /// Build all binaries without running them.
+    pub fn build_bins(profile: Profile) -> Result<(), Error> {
+        let mut args = vec!["build", "--quiet", "--bins"];
+        args.extend(profile.cargo_args());
+
+        let status = Command::new("cargo").args(&args).status()?;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(Error::Build(status))
         }
+    }
+
+    /// Run the given days with the aggregate `aoc_all` binary and collect the reports of their parts.
+    pub fn run_all_in_process(
+        days: &[PuzzleId],
+        is_timed: bool,
+        profile: Profile,
+    ) -> Result<ChildOutput, Error> {
+        let days: Vec<String> = days.iter().map(ToString::to_string).collect();
+        run_bin("aoc_all", &days, is_timed, profile, false, None)
+    }
+
+    fn run_bin(
+        bin: &str,
+        bin_args: &[String],
+        is_timed: bool,
+        profile: Profile,
+        buffer: bool,
+        timeout: Option<Duration>,
+    ) -> Result<ChildOutput, Error> {
+        let mut args = vec!["run", "--quiet", "--bin", bin];
+        args.extend(profile.cargo_args());
+
+        args.push("--");
 
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        if profile == Profile::Dhat {
+            args.push("--memory");
+        }
+
+        args.extend(bin_args.iter().map(String::as_str));
+
+        let report_path = env::temp_dir().join(format!("aoc-report-{bin}-{}.jsonl", process::id()));
+        let _ = fs::remove_file(&report_path);
+
+        // spawn child command with piped stdout/stderr and forward or buffer its output.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .env(REPORT_ENV, &report_path)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -108,150 +448,272 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let stdout_thread = thread::spawn(move || read_lines(stdout, buffer, false));
+        let stderr_thread = thread::spawn(move || read_lines(stderr, buffer, true));
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        let exit = wait(&mut cmd, timeout)?;
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let stdout_lines = stdout_thread.join().unwrap();
+        let stderr_lines = stderr_thread.join().unwrap();
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let reports = PartReport::read_all(&report_path).map_err(Error::Report);
+        let _ = fs::remove_file(&report_path);
 
-        Ok(output)
+        Ok(ChildOutput {
+            stdout: stdout_lines,
+            stderr: stderr_lines,
+            exit,
+            reports: reports?,
+        })
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Forward the lines of a child's output stream, or collect them if `buffer` is set.
+    fn read_lines(reader: impl BufRead, buffer: bool, is_stderr: bool) -> Vec<String> {
+        let mut lines = vec![];
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        for line in reader.lines() {
+            let Ok(line) = line else {
+                break;
+            };
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+            if buffer {
+                lines.push(line);
+            } else if is_stderr {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        lines
+    }
 
-                timings.total_nanos += nanos;
-            });
+    /// Wait for the child to exit, killing it once `timeout` has elapsed.
+    /// NOTE: on unix, `cargo run` replaces itself with the solution binary, so the kill reaches the solution.
+    fn wait(cmd: &mut Child, timeout: Option<Duration>) -> Result<ChildExit, Error> {
+        let Some(timeout) = timeout else {
+            return Ok(cmd.wait()?.into());
+        };
 
-        timings
-    }
+        let deadline = Instant::now() + timeout;
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+        loop {
+            if let Some(status) = cmd.try_wait()? {
+                return Ok(status.into());
+            }
+
+            if Instant::now() >= deadline {
+                cmd.kill()?;
+                cmd.wait()?;
+                return Ok(ChildExit::TimedOut);
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// Derive the status of a day from how its process exited and the reports of its parts.
+    pub fn day_status(exit: &ChildExit, reports: &[PartReport]) -> DayStatus {
+        match exit {
+            ChildExit::TimedOut => DayStatus::Timeout,
+            ChildExit::Failure(_) => DayStatus::Panic,
+            ChildExit::Success => {
+                if reports.iter().any(|r| r.status == PartStatus::Panicked) {
+                    DayStatus::Panic
+                } else if reports.iter().any(|r| r.status == PartStatus::Failed) {
+                    DayStatus::Failed
+                } else if reports
+                    .iter()
+                    .any(|r| r.part != PARSE_PART && r.status == PartStatus::Solved)
+                {
+                    DayStatus::Ok
+                } else {
+                    DayStatus::NotSolved
+                }
+            }
+        }
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
+    /// Build the timing of a day from the reports of its successful parse phase and solved parts.
+    pub fn timing_from_reports(
+        reports: &[PartReport],
+        day: PuzzleId,
+        status: DayStatus,
+    ) -> super::Timing {
+        let mut timings = super::Timing {
+            day,
+            status,
+            ..Default::default()
+        };
+
+        for report in reports.iter().filter(|r| r.status == PartStatus::Solved) {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let timing_str = format!(
+                "{:.1?}",
+                Duration::from_nanos(report.stats.mean_nanos as u64)
             );
-        }};
+
+            match report.part {
+                PARSE_PART => {
+                    timings.parse = Some(timing_str);
+                    timings.parse_stats = Some(report.stats);
+                }
+                1 => {
+                    timings.part_1 = Some(timing_str);
+                    timings.part_1_stats = Some(report.stats);
+                }
+                2 => {
+                    timings.part_2 = Some(timing_str);
+                    timings.part_2_stats = Some(report.stats);
+                }
+                _ => continue,
+            }
+
+            timings.total_nanos += report.stats.mean_nanos;
+        }
+
+        timings
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{day_status, timing_from_reports, ChildExit, PARSE_PART};
+
+        use crate::{
+            puzzle,
+            template::{
+                part_report::{PartReport, PartStatus},
+                timings::{DayStatus, PartStats},
+            },
+        };
 
-        use crate::day;
+        fn report(part: u8, status: PartStatus, answer: &str, mean_nanos: f64) -> PartReport {
+            PartReport {
+                day: puzzle!(1),
+                part,
+                status,
+                answer: Some(answer.into()),
+                error: None,
+                stats: PartStats {
+                    samples: 100.0,
+                    mean_nanos,
+                    median_nanos: mean_nanos,
+                    ..Default::default()
+                },
+                memory: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn builds_timings_from_reports() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(1, PartStatus::Solved, "0", 74.0),
+                    report(2, PartStatus::Solved, "10", 74_130_000.0),
                 ],
-                day!(1),
+                puzzle!(1),
+                DayStatus::Ok,
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 74_130_074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(res.part_2_stats.unwrap().median_nanos, 74_130_000_f64);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn times_parse_phase() {
+            let mut parse = report(PARSE_PART, PartStatus::Solved, "", 1_000.0);
+            parse.answer = None;
+
+            let res = timing_from_reports(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    parse,
+                    report(1, PartStatus::Solved, "1", 10.0),
+                    report(2, PartStatus::Unsolved, "", 10.0),
                 ],
-                day!(1),
+                puzzle!(1),
+                DayStatus::Ok,
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.parse.unwrap(), "1.0µs");
+            assert_eq!(res.parse_stats.unwrap().mean_nanos, 1_000_f64);
+            assert_eq!(res.total_nanos, 1_010_f64);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn ignores_patterns_in_answers() {
+            let res = timing_from_reports(
+                &[report(
+                    1,
+                    PartStatus::Solved,
+                    "@ @ ( ) ms (2s @ 5 samples)\n#..#",
+                    2e9,
+                )],
+                puzzle!(1),
+                DayStatus::Ok,
+            );
+            assert_eq!(res.total_nanos, 2e9);
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn handles_unsolved_parts() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    report(1, PartStatus::Unsolved, "", 10.0),
+                    report(2, PartStatus::Unsolved, "", 10.0),
                 ],
-                day!(1),
+                puzzle!(1),
+                DayStatus::NotSolved,
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_1_stats.is_none(), true);
+            assert_eq!(res.status, DayStatus::NotSolved);
+        }
+
+        #[test]
+        fn derives_day_status() {
+            let solved = [
+                report(1, PartStatus::Solved, "1", 1.0),
+                report(2, PartStatus::Unsolved, "", 1.0),
+            ];
+            let unsolved = [
+                report(PARSE_PART, PartStatus::Solved, "", 1.0),
+                report(1, PartStatus::Unsolved, "", 1.0),
+            ];
+            let panicked = [
+                report(1, PartStatus::Solved, "1", 1.0),
+                report(2, PartStatus::Panicked, "", 0.0),
+            ];
+            let failed = [
+                report(1, PartStatus::Solved, "1", 1.0),
+                report(2, PartStatus::Failed, "", 1.0),
+            ];
+            let failed_parse = [report(PARSE_PART, PartStatus::Failed, "", 1.0)];
+
+            assert_eq!(day_status(&ChildExit::Success, &solved), DayStatus::Ok);
+            assert_eq!(
+                day_status(&ChildExit::Success, &unsolved),
+                DayStatus::NotSolved
+            );
+            assert_eq!(day_status(&ChildExit::Success, &[]), DayStatus::NotSolved);
+            assert_eq!(day_status(&ChildExit::Success, &panicked), DayStatus::Panic);
+            assert_eq!(day_status(&ChildExit::Success, &failed), DayStatus::Failed);
+            assert_eq!(
+                day_status(&ChildExit::Success, &failed_parse),
+                DayStatus::Failed
+            );
+            assert_eq!(
+                day_status(&ChildExit::Failure(Some(101)), &solved),
+                DayStatus::Panic
+            );
+            assert_eq!(day_status(&ChildExit::Failure(None), &[]), DayStatus::Panic);
+            assert_eq!(
+                day_status(&ChildExit::TimedOut, &solved),
+                DayStatus::Timeout
+            );
         }
     }
 }
@@ -1,24 +1,200 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{stdin, stdout, Write};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::answers::Answers;
+use crate::template::part_report::{PartReport, PartStatus};
+use crate::template::submissions::{self, Check, Submissions};
+use crate::template::timings::{MemoryStats, PartStats};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_client, PuzzleId, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Outcome of a single run of a solution part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PartOutcome<T> {
+    Solved(T),
+    /// The part returned `None`, i.e. it is not implemented yet.
+    Unsolved,
+    /// The part returned an error, e.g. for malformed input.
+    Failed(String),
+}
+
+impl<T> PartOutcome<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> PartOutcome<U> {
+        match self {
+            PartOutcome::Solved(x) => PartOutcome::Solved(f(x)),
+            PartOutcome::Unsolved => PartOutcome::Unsolved,
+            PartOutcome::Failed(e) => PartOutcome::Failed(e),
+        }
+    }
+
+    pub fn status(&self) -> PartStatus {
+        match self {
+            PartOutcome::Solved(_) => PartStatus::Solved,
+            PartOutcome::Unsolved => PartStatus::Unsolved,
+            PartOutcome::Failed(_) => PartStatus::Failed,
+        }
+    }
+}
+
+/// Return types accepted for solution parts: `Option<T>` and `Result<T, E>` with displayable `T` and `E`.
+pub trait PartResult {
+    type Answer: Display;
+
+    fn into_outcome(self) -> PartOutcome<Self::Answer>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn into_outcome(self) -> PartOutcome<T> {
+        self.map_or(PartOutcome::Unsolved, PartOutcome::Solved)
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn into_outcome(self) -> PartOutcome<T> {
+        match self {
+            Ok(x) => PartOutcome::Solved(x),
+            Err(e) => PartOutcome::Failed(e.to_string()),
+        }
+    }
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+impl<T: Display> PartResult for PartOutcome<T> {
+    type Answer = T;
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    fn into_outcome(self) -> PartOutcome<T> {
+        self
+    }
+}
+
+/// Part number under which the parse phase of a solution is reported.
+pub const PARSE_PART: u8 = 0;
+
+/// Runs a single phase (parse or part) of a solution, see [`unguarded`] and [`registry::guarded`](crate::template::registry::guarded).
+pub type Guard = fn(PuzzleId, u8, &mut dyn FnMut());
+
+/// Runs the phase as is. Panics abort the whole run.
+pub fn unguarded(_day: PuzzleId, _part: u8, run: &mut dyn FnMut()) {
+    run();
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+/// Name of a phase in the output, e.g. `Parse` or `Part 1`.
+pub fn phase_label(part: u8) -> String {
+    if part == PARSE_PART {
+        "Parse".into()
+    } else {
+        format!("Part {part}")
+    }
+}
+
+/// Run the parse phase of a solution. Returns the parsed input, or `None` if parsing failed.
+pub fn run_parse<I: Clone, T, E: Display>(
+    func: impl Fn(I) -> Result<T, E>,
+    input: I,
+    day: PuzzleId,
+) -> Option<T> {
+    let label = phase_label(PARSE_PART);
+
+    let (result, stats, memory) = run_timed(
+        |input| func(input).map_err(|e| e.to_string()),
+        input,
+        |result| print_result(&parse_outcome(result), &label, ""),
+    );
+
+    let outcome = parse_outcome(&result);
+    print_phase(&outcome, &label, &stats, memory);
+    emit_report(day, PARSE_PART, &outcome, None, &stats, memory);
+
+    result.ok()
+}
+
+/// The parse phase has no answer, a successful parse is shown as a check mark.
+fn parse_outcome<T>(result: &Result<T, String>) -> PartOutcome<&'static str> {
+    match result {
+        Ok(_) => PartOutcome::Solved("✔"),
+        Err(e) => PartOutcome::Failed(e.clone()),
+    }
+}
+
+pub fn run_part<I: Clone, R: PartResult>(func: impl Fn(I) -> R, input: I, day: PuzzleId, part: u8) {
+    let label = phase_label(part);
+
+    let (result, stats, memory) = run_timed(
+        |input| func(input).into_outcome(),
+        input,
+        |result| print_result(result, &label, ""),
+    );
+
+    print_phase(&result, &label, &stats, memory);
+
+    let answer = match &result {
+        PartOutcome::Solved(x) => Some(x.to_string()),
+        _ => None,
+    };
+
+    emit_report(day, part, &result, answer.clone(), &stats, memory);
+
+    if let Some(answer) = answer {
+        match submit_result(&answer, day, part) {
+            Some(Ok(result)) => {
+                println!("{result}");
+                if result == aoc_client::SubmissionResult::Correct {
+                    record_answer(day, part, &answer);
+                }
+            }
+            Some(Err(e)) => eprintln!("Failed to submit answer: {e}"),
+            None => {}
+        }
+    }
+}
+
+fn print_phase<T: Display>(
+    result: &PartOutcome<T>,
+    label: &str,
+    stats: &BenchStats,
+    memory: Option<MemoryStats>,
+) {
+    print_result(result, label, &format_duration(&stats.mean, stats.samples));
+
+    if stats.samples > 1 {
+        println!("{label} stats: {}", format_stats(stats));
+    }
+
+    if let Some(memory) = memory {
+        println!("{label} memory: {memory}");
+    }
+}
+
+fn emit_report<T>(
+    day: PuzzleId,
+    part: u8,
+    result: &PartOutcome<T>,
+    answer: Option<String>,
+    stats: &BenchStats,
+    memory: Option<MemoryStats>,
+) {
+    let report = PartReport {
+        day,
+        part,
+        status: result.status(),
+        answer,
+        error: match result {
+            PartOutcome::Failed(e) => Some(e.clone()),
+            _ => None,
+        },
+        stats: PartStats::from(stats),
+        memory,
+    };
+
+    if let Err(e) = report.emit() {
+        eprintln!("Failed to write part report: {e}");
     }
 }
 
@@ -29,30 +205,122 @@ fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, BenchStats, Option<MemoryStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
 
+        // testing mode only measures, without writing a report file for every part.
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let _profiler = is_measuring_memory().then(|| dhat::Profiler::builder().testing().build());
 
-        func(input)
+        let result = func(input);
+        (result, memory_stats())
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let stats = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        BenchStats::from_samples(&mut [base_time])
     };
 
-    (result, run.0, run.1)
+    (result, stats, memory)
+}
+
+/// Whether the heap usage of every phase is measured, i.e. the solution runs for `cargo time --memory`.
+#[cfg(feature = "dhat-heap")]
+fn is_measuring_memory() -> bool {
+    env::args().any(|x| x == "--memory")
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Start the profiler that writes `dhat-heap.json` for `cargo solve --dhat`.
+/// It covers the whole process, so the report contains every phase of the solution.
+/// Returns `None` when measuring memory, which profiles every phase on its own instead.
+#[cfg(feature = "dhat-heap")]
+pub fn dhat_profiler() -> Option<dhat::Profiler> {
+    (!is_measuring_memory()).then(dhat::Profiler::new_heap)
+}
+
+/// Heap usage of the current phase, if measuring memory with dhat.
+#[cfg(feature = "dhat-heap")]
+fn memory_stats() -> Option<MemoryStats> {
+    if !is_measuring_memory() {
+        return None;
+    }
+
+    let stats = dhat::HeapStats::get();
+
+    Some(MemoryStats {
+        peak_bytes: stats.max_bytes as u64,
+        total_allocations: stats.total_blocks,
+    })
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+fn memory_stats() -> Option<MemoryStats> {
+    None
+}
+
+/// Summary of the sample distribution collected while benching a solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl BenchStats {
+    /// Computes statistics over a non-empty set of samples. Sorts `samples` in place.
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "cannot compute stats of zero samples");
+        samples.sort_unstable();
+
+        let len = samples.len();
+        let nanos = || samples.iter().map(|d| d.as_nanos() as f64);
+        let mean = nanos().sum::<f64>() / len as f64;
+        let variance = nanos().map(|x| (x - mean).powi(2)).sum::<f64>() / len as f64;
+
+        let median = if len.is_multiple_of(2) {
+            (samples[len / 2 - 1] + samples[len / 2]) / 2
+        } else {
+            samples[len / 2]
+        };
+
+        // nearest-rank percentile.
+        let p95_rank = (len * 95).div_ceil(100).max(1);
+
+        Self {
+            samples: len as u128,
+            mean: Duration::from_secs_f64(mean / 1e9),
+            min: samples[0],
+            median,
+            p95: samples[p95_rank - 1],
+            stddev: Duration::from_secs_f64(variance.sqrt() / 1e9),
+        }
+    }
+}
+
+impl From<&BenchStats> for PartStats {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchStats) -> Self {
+        PartStats {
+            samples: value.samples as f64,
+            mean_nanos: value.mean.as_nanos() as f64,
+            min_nanos: value.min.as_nanos() as f64,
+            median_nanos: value.median.as_nanos() as f64,
+            p95_nanos: value.p95.as_nanos() as f64,
+            stddev_nanos: value.stddev.as_nanos() as f64,
+        }
+    }
+}
+
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> BenchStats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -61,6 +329,13 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    // warm-up runs settle caches and lazily spawned thread pools (e.g. rayon) and are not recorded.
+    let warmup_iterations = (bench_iterations / 10).max(1);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input.clone())));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -71,19 +346,7 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    BenchStats::from_samples(&mut timers)
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -94,11 +357,18 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn format_stats(stats: &BenchStats) -> String {
+    format!(
+        "min {:.1?}, median {:.1?}, p95 {:.1?}, stddev {:.1?}",
+        stats.min, stats.median, stats.p95, stats.stddev
+    )
+}
+
+fn print_result<T: Display>(result: &PartOutcome<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        PartOutcome::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -118,7 +388,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        PartOutcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,17 +396,35 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        PartOutcome::Failed(e) => {
+            if is_intermediate_result {
+                print!("{part}: ✖ failed");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ failed: {e}             ");
+            }
+        }
+    }
+}
+
+/// Store an accepted answer in `data/answers.json` so `cargo verify` can check it later.
+fn record_answer(day: PuzzleId, part: u8, answer: &str) {
+    let mut answers = Answers::read_from_file();
+    answers.set(day, part, answer);
+
+    match answers.store_file() {
+        Ok(()) => println!("Recorded accepted answer for day {day}, part {part}."),
+        Err(e) => eprintln!("Failed to record accepted answer: {e}"),
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Parse the arguments passed to `solve` and try to submit one part of the solution if
+/// `--submit <part>` matches the part.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    day: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<aoc_client::SubmissionResult, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +447,104 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let answer = result.to_string();
+    let mut submissions = Submissions::read_from_file();
+
+    match submissions.check(day, part, &answer, submissions::now()) {
+        Check::Allowed => {}
+        Check::Suspicious(reason) => {
+            if !confirm(&format!(
+                "Suspicious answer `{answer}`: {reason} Submit anyway?"
+            )) {
+                println!("Submission cancelled.");
+                return None;
+            }
+        }
+        Check::Refused(reason) => {
+            eprintln!("Not submitting: {reason}");
+            return None;
+        }
+    }
+
+    println!("Submitting result...");
+    let result = aoc_client::submit(day, part, &answer);
+
+    if let Ok(verdict) = &result {
+        submissions.record(day, part, &answer, *verdict);
+        if let Err(e) = submissions.store_file() {
+            eprintln!("Failed to record submission: {e}");
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(result)
+}
+
+/// Ask a yes/no question on the terminal, defaulting to no.
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = stdout().flush();
+
+    let mut line = String::new();
+    if stdin().read_line(&mut line).is_err() {
+        return false;
+    }
+
+    matches!(line.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchStats, PartOutcome, PartResult};
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_stats_for_odd_samples() {
+        let stats = BenchStats::from_samples(&mut nanos(&[30, 10, 20]));
+        assert_eq!(stats.samples, 3);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(20));
+        assert_eq!(stats.mean, Duration::from_nanos(20));
+        assert_eq!(stats.p95, Duration::from_nanos(30));
+    }
+
+    #[test]
+    fn computes_stats_for_even_samples() {
+        let stats = BenchStats::from_samples(&mut nanos(&[4, 2, 8, 6]));
+        assert_eq!(stats.median, Duration::from_nanos(5));
+        assert_eq!(stats.mean, Duration::from_nanos(5));
+        assert_eq!(stats.stddev.as_nanos(), 2);
+    }
+
+    #[test]
+    fn p95_ignores_single_outlier() {
+        let mut samples = vec![Duration::from_nanos(100); 99];
+        samples.push(Duration::from_micros(500));
+        let stats = BenchStats::from_samples(&mut samples);
+        assert_eq!(stats.p95, Duration::from_nanos(100));
+        assert_eq!(stats.median, Duration::from_nanos(100));
+        assert!(stats.mean > stats.median);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&mut nanos(&[42]));
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.p95, Duration::from_nanos(42));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn converts_part_results() {
+        assert_eq!(Some(1).into_outcome(), PartOutcome::Solved(1));
+        assert_eq!(None::<u32>.into_outcome(), PartOutcome::Unsolved);
+        assert_eq!(
+            "x".parse::<u32>().into_outcome(),
+            PartOutcome::Failed("invalid digit found in string".into())
+        );
+        assert_eq!(Ok::<_, String>(2).into_outcome(), PartOutcome::Solved(2));
+    }
 }
@@ -0,0 +1,472 @@
+/// Module that keeps a log of submitted answers and checks new answers against it before they
+/// are sent to the website.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Error},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_client::SubmissionResult, PuzzleId};
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.json";
+
+/// The website blocks submissions for at least a minute after a wrong answer.
+const WRONG_ANSWER_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// A single answer that was sent to the website.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: PuzzleId,
+    pub part: u8,
+    pub answer: String,
+    pub result: SubmissionResult,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Represents all submissions. Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+/// Whether an answer may be submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Allowed,
+    /// The answer is likely a placeholder and should only be submitted after confirmation.
+    Suspicious(String),
+    /// The answer will not be accepted by the website.
+    Refused(String),
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(SUBMISSIONS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns empty submissions.
+    pub fn read_from_file() -> Self {
+        let s = match fs::read_to_string(SUBMISSIONS_FILE_PATH) {
+            Ok(s) => Submissions::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match s {
+            Ok(submissions) => submissions,
+            Err(e) => {
+                eprintln!("{e}");
+                Submissions::default()
+            }
+        }
+    }
+
+    pub fn record(&mut self, day: PuzzleId, part: u8, answer: &str, result: SubmissionResult) {
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            result,
+            timestamp: now(),
+        });
+    }
+
+    /// Check whether `answer` should be submitted at time `now`, given the earlier submissions
+    /// of the same part.
+    pub fn check(&self, day: PuzzleId, part: u8, answer: &str, now: u64) -> Check {
+        let submissions: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect();
+
+        if let Some(correct) = submissions
+            .iter()
+            .find(|s| s.result == SubmissionResult::Correct)
+        {
+            return Check::Refused(format!(
+                "this part was already solved with `{}`.",
+                correct.answer
+            ));
+        }
+
+        if let Some(rejected) = submissions
+            .iter()
+            .find(|s| s.answer == answer && is_wrong(s.result))
+        {
+            return Check::Refused(format!(
+                "`{answer}` was already rejected: {}",
+                rejected.result
+            ));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |result| {
+                submissions
+                    .iter()
+                    .filter(move |s| s.result == result)
+                    .filter_map(|s| s.answer.parse::<i128>().ok())
+            };
+
+            if let Some(high) = bound(SubmissionResult::TooHigh).min() {
+                if value >= high {
+                    return Check::Refused(format!(
+                        "`{answer}` is not lower than `{high}`, which was too high."
+                    ));
+                }
+            }
+
+            if let Some(low) = bound(SubmissionResult::TooLow).max() {
+                if value <= low {
+                    return Check::Refused(format!(
+                        "`{answer}` is not higher than `{low}`, which was too low."
+                    ));
+                }
+            }
+        }
+
+        // the site rate limits the whole account, and locks out the whole puzzle after a wrong answer.
+        let blocked_until = self
+            .data
+            .iter()
+            .filter_map(|s| match s.result {
+                SubmissionResult::RateLimited(wait) => Some(s.timestamp + wait.as_secs()),
+                result if is_wrong(result) && s.day == day => {
+                    Some(s.timestamp + WRONG_ANSWER_COOLDOWN.as_secs())
+                }
+                _ => None,
+            })
+            .max();
+
+        if let Some(blocked_until) = blocked_until.filter(|until| *until > now) {
+            return Check::Refused(format!(
+                "the last submission was too recent, wait {}s before submitting again.",
+                blocked_until - now
+            ));
+        }
+
+        match answer.trim() {
+            "" => Check::Suspicious("the answer is empty.".into()),
+            "0" => Check::Suspicious("the answer is 0.".into()),
+            x if x.starts_with('-') && x[1..].parse::<u128>().is_ok() => {
+                Check::Suspicious("the answer is negative.".into())
+            }
+            _ => Check::Allowed,
+        }
+    }
+}
+
+fn is_wrong(result: SubmissionResult) -> bool {
+    matches!(
+        result,
+        SubmissionResult::Incorrect | SubmissionResult::TooHigh | SubmissionResult::TooLow
+    )
+}
+
+fn result_as_str(result: SubmissionResult) -> &'static str {
+    match result {
+        SubmissionResult::Correct => "correct",
+        SubmissionResult::Incorrect => "incorrect",
+        SubmissionResult::TooHigh => "too_high",
+        SubmissionResult::TooLow => "too_low",
+        SubmissionResult::RateLimited(_) => "rate_limited",
+        SubmissionResult::WrongLevel => "wrong_level",
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "result".into(),
+            JsonValue::String(result_as_str(value.result).into()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        if let SubmissionResult::RateLimited(wait) = value.result {
+            map.insert("wait".into(), JsonValue::Number(wait.as_secs() as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| PuzzleId::from_str(day).ok())
+            .ok_or("Expected submission.day to be a puzzle id.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let result = match json.get("result").and_then(|v| v.get::<String>()) {
+            Some(x) if x == "correct" => SubmissionResult::Correct,
+            Some(x) if x == "incorrect" => SubmissionResult::Incorrect,
+            Some(x) if x == "too_high" => SubmissionResult::TooHigh,
+            Some(x) if x == "too_low" => SubmissionResult::TooLow,
+            Some(x) if x == "rate_limited" => {
+                SubmissionResult::RateLimited(Duration::from_secs(number("wait")?))
+            }
+            Some(x) if x == "wrong_level" => SubmissionResult::WrongLevel,
+            _ => return Err("Expected submission.result to be a known result.".into()),
+        };
+
+        Ok(Submission {
+            day,
+            part: u8::try_from(number("part")?).map_err(|e| e.to_string())?,
+            answer: answer.clone(),
+            result,
+            timestamp: number("timestamp")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Check, Submission, Submissions};
+    use crate::{puzzle, template::aoc_client::SubmissionResult};
+
+    const NOW: u64 = 1_733_029_200;
+
+    fn submission(part: u8, answer: &str, result: SubmissionResult, timestamp: u64) -> Submission {
+        Submission {
+            day: puzzle!(1),
+            part,
+            answer: answer.into(),
+            result,
+            timestamp,
+        }
+    }
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                submission(1, "100", SubmissionResult::TooHigh, NOW - 600),
+                submission(1, "20", SubmissionResult::TooLow, NOW - 500),
+                submission(1, "50", SubmissionResult::Incorrect, NOW - 400),
+                submission(2, "7", SubmissionResult::Correct, NOW - 300),
+            ],
+        }
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let submissions = get_mock_submissions();
+
+        assert!(matches!(
+            submissions.check(puzzle!(1), 1, "50", NOW),
+            Check::Refused(_)
+        ));
+        assert_eq!(submissions.check(puzzle!(1), 1, "51", NOW), Check::Allowed);
+        assert_eq!(submissions.check(puzzle!(2), 1, "50", NOW), Check::Allowed);
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let submissions = get_mock_submissions();
+
+        assert_eq!(
+            submissions.check(puzzle!(1), 1, "150", NOW),
+            Check::Refused("`150` is not lower than `100`, which was too high.".into())
+        );
+        assert_eq!(
+            submissions.check(puzzle!(1), 1, "5", NOW),
+            Check::Refused("`5` is not higher than `20`, which was too low.".into())
+        );
+        assert_eq!(submissions.check(puzzle!(1), 1, "99", NOW), Check::Allowed);
+        assert_eq!(submissions.check(puzzle!(1), 1, "21", NOW), Check::Allowed);
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        assert_eq!(
+            get_mock_submissions().check(puzzle!(1), 2, "8", NOW),
+            Check::Refused("this part was already solved with `7`.".into())
+        );
+    }
+
+    #[test]
+    fn enforces_cooldown() {
+        let mut submissions = get_mock_submissions();
+
+        submissions
+            .data
+            .push(submission(1, "60", SubmissionResult::Incorrect, NOW - 10));
+        assert_eq!(
+            submissions.check(puzzle!(1), 1, "61", NOW),
+            Check::Refused(
+                "the last submission was too recent, wait 50s before submitting again.".into()
+            )
+        );
+        assert_eq!(
+            submissions.check(puzzle!(1), 1, "61", NOW + 50),
+            Check::Allowed
+        );
+
+        submissions.data.push(submission(
+            1,
+            "61",
+            SubmissionResult::RateLimited(Duration::from_secs(300)),
+            NOW,
+        ));
+        assert!(matches!(
+            submissions.check(puzzle!(1), 1, "61", NOW + 60),
+            Check::Refused(_)
+        ));
+        assert_eq!(
+            submissions.check(puzzle!(1), 1, "61", NOW + 300),
+            Check::Allowed
+        );
+    }
+
+    #[test]
+    fn enforces_cooldown_across_parts() {
+        let mut submissions = Submissions {
+            data: vec![submission(1, "60", SubmissionResult::Incorrect, NOW - 10)],
+        };
+
+        // a wrong answer locks out the whole puzzle.
+        assert!(matches!(
+            submissions.check(puzzle!(1), 2, "61", NOW),
+            Check::Refused(_)
+        ));
+        assert_eq!(submissions.check(puzzle!(2), 1, "61", NOW), Check::Allowed);
+
+        // rate limits apply to the whole account.
+        submissions.data.push(submission(
+            1,
+            "61",
+            SubmissionResult::RateLimited(Duration::from_secs(300)),
+            NOW,
+        ));
+        assert!(matches!(
+            submissions.check(puzzle!(2), 1, "61", NOW + 60),
+            Check::Refused(_)
+        ));
+    }
+
+    #[test]
+    fn flags_suspicious_answers() {
+        let submissions = Submissions::default();
+
+        assert!(matches!(
+            submissions.check(puzzle!(3), 1, "0", NOW),
+            Check::Suspicious(_)
+        ));
+        assert!(matches!(
+            submissions.check(puzzle!(3), 1, "-12", NOW),
+            Check::Suspicious(_)
+        ));
+        assert!(matches!(
+            submissions.check(puzzle!(3), 1, "", NOW),
+            Check::Suspicious(_)
+        ));
+        assert_eq!(
+            submissions.check(puzzle!(3), 1, "4,6,3", NOW),
+            Check::Allowed
+        );
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let mut submissions = get_mock_submissions();
+        submissions.data.push(submission(
+            1,
+            "61",
+            SubmissionResult::RateLimited(Duration::from_secs(300)),
+            NOW,
+        ));
+
+        let json = tinyjson::JsonValue::from(submissions.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+
+        assert_eq!(parsed.data, submissions.data);
+    }
+}
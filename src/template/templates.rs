@@ -0,0 +1,280 @@
+/// Module that renders the solution files of `cargo scaffold` from templates.
+///
+/// Templates are looked up by name, first in the user template directory and then among the
+/// built-in templates. A user template thus replaces a built-in template of the same name.
+use std::{env, fmt::Display, fs, io, path::PathBuf};
+
+use chrono::Utc;
+
+use crate::template::{PuzzleId, MAIN_YEAR};
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// Directory of the user-defined templates, can be overridden with `AOC_TEMPLATE_DIR`.
+static TEMPLATE_DIR: &str = "./templates";
+
+/// The built-in templates, with a short description each.
+static BUILTIN_TEMPLATES: [(&str, &str, &str); 5] = [
+    (
+        "default",
+        "parts that take the raw input",
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt")),
+    ),
+    (
+        "grid",
+        "input parsed once into a `Grid<char>`",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "parse",
+        "input parsed once and shared by both parts",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/parse.txt"
+        )),
+    ),
+    (
+        "string",
+        "parts that return text answers",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/string.txt"
+        )),
+    ),
+    (
+        "u64",
+        "parts that return `u64` answers",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/u64.txt"
+        )),
+    ),
+];
+
+#[derive(Debug)]
+pub enum TemplateError {
+    NotFound(String),
+    IO(io::Error),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::NotFound(name) => {
+                write!(f, "unknown template `{name}`, available templates:")?;
+                for (name, description) in available() {
+                    write!(f, "\n  {name:<10} {description}")?;
+                }
+                Ok(())
+            }
+            TemplateError::IO(e) => write!(f, "could not read template: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for TemplateError {
+    fn from(e: io::Error) -> Self {
+        TemplateError::IO(e)
+    }
+}
+
+fn template_dir() -> PathBuf {
+    env::var_os("AOC_TEMPLATE_DIR").map_or_else(|| PathBuf::from(TEMPLATE_DIR), PathBuf::from)
+}
+
+/// Names and descriptions of all templates, sorted by name.
+pub fn available() -> Vec<(String, String)> {
+    let mut templates: Vec<(String, String)> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, description, _)| ((*name).to_string(), (*description).to_string()))
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(template_dir()) {
+        for entry in entries.flatten() {
+            let Some(name) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_suffix(".txt"))
+                .map(String::from)
+            else {
+                continue;
+            };
+
+            templates.retain(|(existing, _)| *existing != name);
+            let description = format!("user template in {}", entry.path().display());
+            templates.push((name, description));
+        }
+    }
+
+    templates.sort();
+    templates
+}
+
+/// Load the template `name`, preferring the user template directory over the built-in templates.
+pub fn load(name: &str) -> Result<String, TemplateError> {
+    match fs::read_to_string(template_dir().join(format!("{name}.txt"))) {
+        Ok(template) => return Ok(template),
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+        Err(_) => {}
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, ..)| *builtin == name)
+        .map(|(.., template)| (*template).to_string())
+        .ok_or_else(|| TemplateError::NotFound(name.into()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The values that are substituted into a template.
+pub struct Variables {
+    pub puzzle: PuzzleId,
+    /// Year of the puzzle, falls back to [`MAIN_YEAR`] for puzzles of the main event.
+    pub year: Option<u16>,
+    /// Title of the puzzle, if its description was downloaded to `data/puzzles` before.
+    pub title: Option<String>,
+    /// Date of scaffolding, e.g. `2024-12-01`.
+    pub date: String,
+}
+
+impl Variables {
+    pub fn collect(puzzle: PuzzleId) -> Self {
+        let year = puzzle.year.or(MAIN_YEAR);
+        let title = fs::read_to_string(puzzle.data_path("puzzles", "md"))
+            .ok()
+            .and_then(|description| title_from_markdown(&description));
+
+        Variables {
+            puzzle,
+            year,
+            title,
+            date: Utc::now().format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+/// Substitute the variables of a template:
+///
+/// - `%PUZZLE%`: the argument of `solution!`, e.g. `5` or `2023 / 5`.
+/// - `%DAY_NUMBER%`: the day, e.g. `5`.
+/// - `%DAY%`: the day with two digits, e.g. `05`.
+/// - `%YEAR%`: the year, empty if unknown.
+/// - `%TITLE%`: the title of the puzzle, e.g. `Historian Hysteria`, or `Day 5` if unknown.
+/// - `%DATE%`: the date of scaffolding, e.g. `2024-12-01`.
+pub fn render(template: &str, variables: &Variables) -> String {
+    let Variables {
+        puzzle,
+        year,
+        ref title,
+        ref date,
+    } = *variables;
+    let day = puzzle.day.into_inner();
+
+    let values = [
+        (
+            "%PUZZLE%",
+            match puzzle.year {
+                Some(year) => format!("{year} / {day}"),
+                None => day.to_string(),
+            },
+        ),
+        ("%DAY_NUMBER%", day.to_string()),
+        ("%DAY%", puzzle.day.to_string()),
+        (
+            "%YEAR%",
+            year.map(|year| year.to_string()).unwrap_or_default(),
+        ),
+        ("%TITLE%", title.clone().unwrap_or_else(|| puzzle.title())),
+        ("%DATE%", date.clone()),
+    ];
+
+    values
+        .iter()
+        .fold(template.to_string(), |out, (name, value)| {
+            out.replace(name, value)
+        })
+}
+
+/// Extract the title from a puzzle description, e.g. `Historian Hysteria` from the heading
+/// `## --- Day 1: Historian Hysteria ---`.
+fn title_from_markdown(description: &str) -> Option<String> {
+    let heading = description
+        .lines()
+        .find_map(|line| line.strip_prefix("## --- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    let title = title.trim_end_matches('-').trim();
+
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load, render, title_from_markdown, TemplateError, Variables, BUILTIN_TEMPLATES};
+    use crate::{matches, puzzle};
+
+    fn variables() -> Variables {
+        Variables {
+            puzzle: puzzle!(2023 / 5),
+            year: Some(2023),
+            title: Some("If You Give A Seed A Fertilizer".into()),
+            date: "2023-12-05".into(),
+        }
+    }
+
+    #[test]
+    fn renders_variables() {
+        let template = "solution!(%PUZZLE%); // %YEAR% day %DAY_NUMBER% (%DAY%): %TITLE%, %DATE%";
+
+        assert_eq!(
+            render(template, &variables()),
+            "solution!(2023 / 5); // 2023 day 5 (05): If You Give A Seed A Fertilizer, 2023-12-05"
+        );
+
+        let unknown = Variables {
+            puzzle: puzzle!(5),
+            year: None,
+            title: None,
+            date: "2024-10-01".into(),
+        };
+        assert_eq!(
+            render(template, &unknown),
+            "solution!(5); //  day 5 (05): Day 5, 2024-10-01"
+        );
+    }
+
+    #[test]
+    fn extracts_titles() {
+        assert_eq!(
+            title_from_markdown("## --- Day 1: Historian Hysteria ---\n\nThe *Chief Historian*..."),
+            Some("Historian Hysteria".into())
+        );
+        assert_eq!(title_from_markdown("## --- Part Two ---"), None);
+        assert_eq!(title_from_markdown(""), None);
+    }
+
+    #[test]
+    fn loads_builtin_templates() {
+        for (name, ..) in BUILTIN_TEMPLATES {
+            let template = load(name).unwrap();
+            assert!(template.contains("advent_of_code::solution!(%PUZZLE%"));
+            assert!(template.contains("\"examples\", PUZZLE"));
+
+            let rendered = render(&template, &variables());
+            assert!(rendered.starts_with(
+                "// 2023 day 5: If You Give A Seed A Fertilizer (scaffolded on 2023-12-05)\n"
+            ));
+            assert!(!matches(r"%[A-Z_]+%", &rendered), "{name}: {rendered}");
+        }
+
+        assert!(matches!(
+            load("does-not-exist"),
+            Err(TemplateError::NotFound(_))
+        ));
+    }
+}
@@ -1,19 +1,153 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::PuzzleId;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
-    pub day: Day,
+    pub day: PuzzleId,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<PartStats>,
+    pub part_2_stats: Option<PartStats>,
+    /// Timing of the parse phase, for days that parse their input once for both parts.
+    pub parse: Option<String>,
+    pub parse_stats: Option<PartStats>,
+    /// Heap usage of each phase, only present for timings recorded with `--memory`.
+    pub parse_memory: Option<MemoryStats>,
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
+    pub status: DayStatus,
     pub total_nanos: f64,
 }
 
+/// An empty timing of day 1, to be completed with struct update syntax.
+impl Default for Timing {
+    fn default() -> Self {
+        Timing {
+            day: crate::puzzle!(1),
+            part_1: None,
+            part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse: None,
+            parse_stats: None,
+            parse_memory: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            status: DayStatus::default(),
+            total_nanos: 0_f64,
+        }
+    }
+}
+
+impl Timing {
+    /// Whether the timing was recorded with `--memory`.
+    pub fn has_memory(&self) -> bool {
+        self.parse_memory.is_some() || self.part_1_memory.is_some() || self.part_2_memory.is_some()
+    }
+}
+
+/// Outcome of running the solution of a single day.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DayStatus {
+    #[default]
+    Ok,
+    /// The solution ran, but did not solve any part.
+    NotSolved,
+    /// A part returned an error.
+    Failed,
+    /// The solution panicked or otherwise exited with a non-zero status.
+    Panic,
+    /// The solution was killed after exceeding the timeout.
+    Timeout,
+}
+
+impl DayStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DayStatus::Ok => "ok",
+            DayStatus::NotSolved => "not_solved",
+            DayStatus::Failed => "failed",
+            DayStatus::Panic => "panic",
+            DayStatus::Timeout => "timeout",
+        }
+    }
+}
+
+impl FromStr for DayStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ok" => Ok(DayStatus::Ok),
+            "not_solved" => Ok(DayStatus::NotSolved),
+            "failed" => Ok(DayStatus::Failed),
+            "panic" => Ok(DayStatus::Panic),
+            "timeout" => Ok(DayStatus::Timeout),
+            x => Err(format!("unknown day status `{x}`.")),
+        }
+    }
+}
+
+impl Display for DayStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayStatus::Ok => write!(f, "OK"),
+            DayStatus::NotSolved => write!(f, "NOT SOLVED"),
+            DayStatus::Failed => write!(f, "FAILED"),
+            DayStatus::Panic => write!(f, "PANIC"),
+            DayStatus::Timeout => write!(f, "TIMEOUT"),
+        }
+    }
+}
+
+/// Distribution of the benchmark samples of a single part, in nanoseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PartStats {
+    pub samples: f64,
+    pub mean_nanos: f64,
+    pub min_nanos: f64,
+    pub median_nanos: f64,
+    pub p95_nanos: f64,
+    pub stddev_nanos: f64,
+}
+
+/// Heap usage of a single part, as measured by dhat.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Maximum number of bytes allocated at the same time.
+    pub peak_bytes: u64,
+    /// Number of allocations over the whole run.
+    pub total_allocations: u64,
+}
+
+impl Display for MemoryStats {
+    #[allow(clippy::cast_precision_loss)]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+        if self.peak_bytes < 1024 {
+            write!(f, "{} B", self.peak_bytes)?;
+        } else {
+            let mut size = self.peak_bytes as f64 / 1024_f64;
+            let mut unit = 0;
+
+            while size >= 1024_f64 && unit < UNITS.len() - 1 {
+                size /= 1024_f64;
+                unit += 1;
+            }
+
+            write!(f, "{size:.1} {}", UNITS[unit])?;
+        }
+
+        write!(f, " / {} allocs", self.total_allocations)
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -49,7 +183,18 @@ impl Timings {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+
+            // timings stored without `--memory` keep the memory stats stored before.
+            if !timing.has_memory() {
+                if let Some(old) = self.data.iter().find(|t| t.day == timing.day) {
+                    timing.parse_memory = old.parse_memory;
+                    timing.part_1_memory = old.part_1_memory;
+                    timing.part_2_memory = old.part_2_memory;
+                }
+            }
+
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -58,7 +203,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -67,7 +212,7 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    pub fn is_day_complete(&self, day: PuzzleId) -> bool {
         self.data
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
@@ -120,6 +265,10 @@ impl From<&Timing> for JsonValue {
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
 
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
@@ -140,6 +289,37 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "part_1_stats".into(),
+            value.part_1_stats.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value.part_2_stats.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "parse_stats".into(),
+            value.parse_stats.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        for (key, memory) in [
+            ("parse_memory", value.parse_memory),
+            ("part_1_memory", value.part_1_memory),
+            ("part_2_memory", value.part_2_memory),
+        ] {
+            map.insert(key.into(), memory.map_or(JsonValue::Null, JsonValue::from));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -155,8 +335,8 @@ impl TryFrom<&JsonValue> for Timing {
         let day = json
             .get("day")
             .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
+            .and_then(|day| PuzzleId::from_str(day).ok())
+            .ok_or("Expected timing.day to be a puzzle id.")?;
 
         let part_1 = json
             .get("part_1")
@@ -173,10 +353,46 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // status, stats, the parse phase and memory are absent in timings stored by older versions.
+        let status = match json.get("status").and_then(|v| v.get::<String>()) {
+            Some(status) => status.parse()?,
+            None => DayStatus::Ok,
+        };
+
+        let part_1_stats = match json.get("part_1_stats") {
+            Some(v) if !v.is_null() => Some(PartStats::try_from(v)?),
+            _ => None,
+        };
+
+        let part_2_stats = match json.get("part_2_stats") {
+            Some(v) if !v.is_null() => Some(PartStats::try_from(v)?),
+            _ => None,
+        };
+
+        let parse = json.get("parse").and_then(|v| v.get::<String>()).cloned();
+
+        let parse_stats = match json.get("parse_stats") {
+            Some(v) if !v.is_null() => Some(PartStats::try_from(v)?),
+            _ => None,
+        };
+
+        let memory = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => MemoryStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats,
+            part_2_stats,
+            parse,
+            parse_stats,
+            parse_memory: memory("parse_memory")?,
+            part_1_memory: memory("part_1_memory")?,
+            part_2_memory: memory("part_2_memory")?,
+            status,
             total_nanos,
         })
     }
@@ -184,39 +400,155 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
-mod tests {
-    use crate::day;
+const PART_STATS_KEYS: [&str; 6] = [
+    "samples",
+    "mean_nanos",
+    "min_nanos",
+    "median_nanos",
+    "p95_nanos",
+    "stddev_nanos",
+];
+
+impl From<PartStats> for JsonValue {
+    fn from(value: PartStats) -> Self {
+        let values = [
+            value.samples,
+            value.mean_nanos,
+            value.min_nanos,
+            value.median_nanos,
+            value.p95_nanos,
+            value.stddev_nanos,
+        ];
+
+        let map: HashMap<String, JsonValue> = PART_STATS_KEYS
+            .iter()
+            .zip(values)
+            .map(|(key, value)| ((*key).into(), JsonValue::Number(value)))
+            .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part stats to be a JSON object.")?;
+
+        let mut values = [0_f64; 6];
+
+        for (key, value) in PART_STATS_KEYS.iter().zip(values.iter_mut()) {
+            *value = json
+                .get(*key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or_else(|| format!("Expected part stats.{key} to be a number."))?;
+        }
+
+        let [samples, mean_nanos, min_nanos, median_nanos, p95_nanos, stddev_nanos] = values;
+
+        Ok(PartStats {
+            samples,
+            mean_nanos,
+            min_nanos,
+            median_nanos,
+            p95_nanos,
+            stddev_nanos,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<MemoryStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "total_allocations".into(),
+            JsonValue::Number(value.total_allocations as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or_else(|| format!("Expected memory stats.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: number("peak_bytes")?,
+            total_allocations: number("total_allocations")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
 
+/// Timings shared by the tests of the modules that store and render them.
+#[cfg(feature = "test_lib")]
+pub(crate) mod fixtures {
     use super::{Timing, Timings};
+    use crate::puzzle;
 
-    fn get_mock_timings() -> Timings {
+    /// Days 1, 2 and 4 with both parts solved, day 2 with a parse phase.
+    pub fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    day: puzzle!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
-                    day: day!(2),
+                    day: puzzle!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse: Some("5ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
-                    day: day!(4),
+                    day: puzzle!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
+                    part_2: Some("50ms".into()),
+                    total_nanos: 9e+10,
+                    ..Default::default()
                 },
             ],
         }
     }
+}
 
+#[cfg(feature = "test_lib")]
+mod tests {
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            puzzle,
+            template::timings::{DayStatus, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -224,12 +556,40 @@ mod tests {
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
-            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.day, puzzle!(1));
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "mean_nanos": 1000000, "min_nanos": 900000, "median_nanos": 950000, "p95_nanos": 1200000, "stddev_nanos": 5000 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.unwrap();
+            assert_eq!(stats.samples, 10_f64);
+            assert_eq!(stats.median_nanos, 950_000_f64);
+            assert_eq!(stats.p95_nanos, 1_200_000_f64);
+            assert!(timing.part_2_stats.is_none());
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1, "part_1_stats": { "samples": 10 } }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
+        #[test]
+        fn handles_json_timings_with_status() {
+            let json = r#"{ "data": [{ "day": "14", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "status": "timeout" }, { "day": "15", "part_1": null, "part_2": null, "total_nanos": 0 }, { "day": "16", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "status": "failed" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].status, DayStatus::Timeout);
+            assert_eq!(timings.data[1].status, DayStatus::Ok);
+            assert_eq!(timings.data[2].status, DayStatus::Failed);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -253,7 +613,7 @@ mod tests {
     }
 
     mod serialization {
-        use super::get_mock_timings;
+        use crate::template::timings::fixtures::get_mock_timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -275,9 +635,53 @@ mod tests {
         }
     }
 
+    mod roundtrip {
+        use crate::{
+            puzzle,
+            template::timings::{MemoryStats, PartStats, Timing, Timings},
+        };
+        use tinyjson::JsonValue;
+
+        #[test]
+        fn preserves_stats() {
+            let stats = PartStats {
+                samples: 100.0,
+                mean_nanos: 12.0,
+                min_nanos: 10.0,
+                median_nanos: 11.0,
+                p95_nanos: 20.0,
+                stddev_nanos: 1.5,
+            };
+            let memory = MemoryStats {
+                peak_bytes: 2048,
+                total_allocations: 12,
+            };
+            let timings = Timings {
+                data: vec![Timing {
+                    day: puzzle!(3),
+                    part_1: Some("12.0ns".into()),
+                    part_1_stats: Some(stats),
+                    parse: Some("1.0ns".into()),
+                    parse_stats: Some(stats),
+                    part_1_memory: Some(memory),
+                    total_nanos: 12.0,
+                    ..Default::default()
+                }],
+            };
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].part_1_stats, Some(stats));
+            assert_eq!(parsed.data[0].part_2_stats, None);
+            assert_eq!(parsed.data[0].parse, Some("1.0ns".into()));
+            assert_eq!(parsed.data[0].parse_stats, Some(stats));
+            assert_eq!(parsed.data[0].part_1_memory, Some(memory));
+            assert_eq!(parsed.data[0].part_2_memory, None);
+        }
+    }
+
     mod is_day_complete {
         use crate::{
-            day,
+            puzzle,
             template::timings::{Timing, Timings},
         };
 
@@ -285,70 +689,67 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    day: puzzle!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(puzzle!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    day: puzzle!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(puzzle!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
+                    day: puzzle!(1),
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(puzzle!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
-            day,
-            template::timings::{Timing, Timings},
+            puzzle,
+            template::timings::{MemoryStats, Timing, Timings},
         };
 
-        use super::get_mock_timings;
+        use crate::template::timings::fixtures::get_mock_timings;
 
         #[test]
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    day: puzzle!(3),
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[2].day, day!(3));
-            assert_eq!(merged.data[3].day, day!(4));
+            assert_eq!(merged.data[0].day, puzzle!(1));
+            assert_eq!(merged.data[1].day, puzzle!(2));
+            assert_eq!(merged.data[2].day, puzzle!(3));
+            assert_eq!(merged.data[3].day, puzzle!(4));
         }
 
         #[test]
@@ -357,19 +758,42 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    day: puzzle!(2),
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[0].day, puzzle!(1));
+            assert_eq!(merged.data[1].day, puzzle!(2));
             assert_eq!(merged.data[1].total_nanos, 0_f64);
-            assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.data[2].day, puzzle!(4));
+        }
+
+        #[test]
+        fn keeps_memory_of_timings_without_memory() {
+            let mut timings = get_mock_timings();
+            let memory = MemoryStats {
+                peak_bytes: 1024,
+                total_allocations: 3,
+            };
+            timings.data[1].part_1_memory = Some(memory);
+
+            let mut other = get_mock_timings();
+            other.data[1].total_nanos = 0_f64;
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].part_1_memory, Some(memory));
+            assert_eq!(merged.data[0].part_1_memory, None);
+
+            let mut replaced = get_mock_timings();
+            replaced.data[1].part_2_memory = Some(memory);
+
+            let merged = timings.merge(&replaced);
+            assert_eq!(merged.data[1].part_1_memory, None);
+            assert_eq!(merged.data[1].part_2_memory, Some(memory));
         }
 
         #[test]